@@ -0,0 +1,223 @@
+use amethyst::{
+    core::nalgebra::Vector2,
+    ecs::{
+        Component,
+        DenseVecStorage
+    }
+};
+use serde::{Serialize, Deserialize};
+use crate::components::Rect;
+
+const CIRCLE_OUTLINE_SEGMENTS: usize = 16;
+
+// Shape offsets are relative to the entity position, shapes should fit inside its Rect
+// because the Rect is still used by the broadphase
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum HitboxShape {
+    Circle {
+        #[serde(default)]
+        x: f32,
+        #[serde(default)]
+        y: f32,
+        radius: f32
+    },
+    Rect {
+        #[serde(default)]
+        x: f32,
+        #[serde(default)]
+        y: f32,
+        width: f32,
+        height: f32
+    },
+    // Points of a convex polygon, in order
+    Polygon {
+        points: Vec<[f32; 2]>
+    }
+}
+
+enum WorldShape {
+    Circle(Vector2<f32>, f32),
+    Polygon(Vec<Vector2<f32>>)
+}
+
+impl HitboxShape {
+
+    pub fn from_rect(rect: &Rect) -> Self {
+        return HitboxShape::Rect {
+            x: 0.0,
+            y: 0.0,
+            width: rect.width,
+            height: rect.height
+        };
+    }
+
+    pub fn scaled(&self, scale: f32) -> Self {
+        return match self {
+            HitboxShape::Circle { x, y, radius } => HitboxShape::Circle {
+                x: x * scale,
+                y: y * scale,
+                radius: radius * scale
+            },
+            HitboxShape::Rect { x, y, width, height } => HitboxShape::Rect {
+                x: x * scale,
+                y: y * scale,
+                width: width * scale,
+                height: height * scale
+            },
+            HitboxShape::Polygon { points } => HitboxShape::Polygon {
+                points: points
+                    .iter()
+                    .map(|point| [point[0] * scale, point[1] * scale])
+                    .collect()
+            }
+        };
+    }
+
+    // Closed outline in world space for drawing, circles are approximated
+    pub fn get_outline(&self, position: Vector2<f32>) -> Vec<Vector2<f32>> {
+        return match self.to_world(position) {
+            WorldShape::Circle(center, radius) => (0..CIRCLE_OUTLINE_SEGMENTS)
+                .map(|i| {
+                    let angle = i as f32 / CIRCLE_OUTLINE_SEGMENTS as f32 * 2.0 * std::f32::consts::PI;
+                    center + Vector2::new(angle.cos(), angle.sin()) * radius
+                })
+                .collect(),
+            WorldShape::Polygon(points) => points
+        };
+    }
+
+    fn to_world(&self, position: Vector2<f32>) -> WorldShape {
+        return match self {
+            HitboxShape::Circle { x, y, radius } => WorldShape::Circle(position + Vector2::new(*x, *y), *radius),
+            HitboxShape::Rect { x, y, width, height } => {
+                let center = position + Vector2::new(*x, *y);
+                let half_width = width / 2.0;
+                let half_height = height / 2.0;
+                WorldShape::Polygon(vec![
+                    center + Vector2::new(-half_width, -half_height),
+                    center + Vector2::new(half_width, -half_height),
+                    center + Vector2::new(half_width, half_height),
+                    center + Vector2::new(-half_width, half_height)
+                ])
+            },
+            HitboxShape::Polygon { points } => WorldShape::Polygon(
+                points
+                    .iter()
+                    .map(|point| position + Vector2::new(point[0], point[1]))
+                    .collect()
+            )
+        };
+    }
+
+}
+
+pub struct Hitbox {
+    pub shapes: Vec<HitboxShape>
+}
+
+impl Hitbox {
+
+    pub fn new(shapes: Vec<HitboxShape>) -> Self {
+        return Self {
+            shapes
+        };
+    }
+
+    pub fn is_colliding(
+        first_shapes: &[HitboxShape],
+        first_position: Vector2<f32>,
+        second_shapes: &[HitboxShape],
+        second_position: Vector2<f32>
+    ) -> bool {
+        let second_world_shapes: Vec<WorldShape> = second_shapes
+            .iter()
+            .map(|shape| shape.to_world(second_position))
+            .collect();
+
+        return first_shapes
+            .iter()
+            .map(|shape| shape.to_world(first_position))
+            .any(|first_shape| second_world_shapes
+                .iter()
+                .any(|second_shape| is_shapes_intersecting(&first_shape, second_shape))
+            );
+    }
+
+}
+
+impl Component for Hitbox {
+    type Storage = DenseVecStorage<Self>;
+}
+
+fn is_shapes_intersecting(first: &WorldShape, second: &WorldShape) -> bool {
+    return match (first, second) {
+        (WorldShape::Circle(first_center, first_radius), WorldShape::Circle(second_center, second_radius)) => {
+            let radius = first_radius + second_radius;
+            (second_center - first_center).norm_squared() <= radius * radius
+        },
+        (WorldShape::Circle(center, radius), WorldShape::Polygon(points)) |
+        (WorldShape::Polygon(points), WorldShape::Circle(center, radius)) => {
+            is_circle_polygon_intersecting(*center, *radius, points)
+        },
+        (WorldShape::Polygon(first_points), WorldShape::Polygon(second_points)) => {
+            is_polygons_intersecting(first_points, second_points)
+        }
+    };
+}
+
+// Separating axis theorem, axes are the normals of polygon edges
+fn get_polygon_axes(points: &[Vector2<f32>]) -> Vec<Vector2<f32>> {
+    return (0..points.len())
+        .map(|i| {
+            let edge = points[(i + 1) % points.len()] - points[i];
+            Vector2::new(-edge.y, edge.x)
+        })
+        .filter(|normal| normal.norm_squared() > 0.0)
+        .map(|normal| normal.normalize())
+        .collect();
+}
+
+fn project_polygon(points: &[Vector2<f32>], axis: &Vector2<f32>) -> (f32, f32) {
+    return points
+        .iter()
+        .map(|point| point.dot(axis))
+        .fold((std::f32::MAX, std::f32::MIN), |(min, max), projection| (min.min(projection), max.max(projection)));
+}
+
+fn is_polygons_intersecting(first_points: &[Vector2<f32>], second_points: &[Vector2<f32>]) -> bool {
+    return get_polygon_axes(first_points)
+        .iter()
+        .chain(get_polygon_axes(second_points).iter())
+        .all(|axis| {
+            let (first_min, first_max) = project_polygon(first_points, axis);
+            let (second_min, second_max) = project_polygon(second_points, axis);
+            first_min <= second_max && second_min <= first_max
+        });
+}
+
+fn is_circle_polygon_intersecting(center: Vector2<f32>, radius: f32, points: &[Vector2<f32>]) -> bool {
+    let mut axes = get_polygon_axes(points);
+    // Extra axis from circle center towards the closest polygon vertex
+    let closest_point = points
+        .iter()
+        .min_by(|first, second| {
+            (*first - center).norm_squared()
+                .partial_cmp(&(*second - center).norm_squared())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+    if let Some(closest_point) = closest_point {
+        let axis = closest_point - center;
+        if axis.norm_squared() > 0.0 {
+            axes.push(axis.normalize());
+        }
+    }
+
+    return axes
+        .iter()
+        .all(|axis| {
+            let (polygon_min, polygon_max) = project_polygon(points, axis);
+            let circle_projection = center.dot(axis);
+            polygon_min <= circle_projection + radius && circle_projection - radius <= polygon_max
+        });
+}
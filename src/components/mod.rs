@@ -3,12 +3,15 @@ use amethyst::{
     ecs::{
         Component,
         DenseVecStorage,
-        world::Index
-    }
+        Entity
+    },
+    renderer::SpriteRender
 };
+use serde::{Serialize, Deserialize};
+use crate::constants;
 
+pub mod hitbox;
 pub mod tags;
-pub mod ui;
 
 pub struct Rect {
     pub width: f32,
@@ -19,6 +22,19 @@ impl Component for Rect {
     type Storage = DenseVecStorage<Self>;
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum CollisionLayer {
+    Player,
+    Enemy,
+    PlayerProjectile,
+    EnemyProjectile,
+    Pickup
+}
+
+impl Component for CollisionLayer {
+    type Storage = DenseVecStorage<Self>;
+}
+
 pub struct Moveable {
     pub move_speed: f32,
     pub direction: Vector2<f32>
@@ -28,12 +44,43 @@ impl Component for Moveable {
     type Storage = DenseVecStorage<Self>;
 }
 
+pub struct RigidBody {
+    pub velocity: Vector2<f32>,
+    pub angular_velocity: f32,
+    pub mass: f32,
+    pub linear_damping: f32,
+    pub angular_damping: f32
+}
+
+impl RigidBody {
+
+    pub fn new(mass: f32) -> Self {
+        return Self {
+            velocity: Vector2::new(0.0, 0.0),
+            angular_velocity: 0.0,
+            mass,
+            linear_damping: 3.0,
+            angular_damping: 0.0
+        };
+    }
+
+    pub fn apply_impulse(&mut self, impulse: Vector2<f32>) {
+        self.velocity += impulse / self.mass;
+    }
+
+}
+
+impl Component for RigidBody {
+    type Storage = DenseVecStorage<Self>;
+}
+
 pub struct Killable {
     health: i32,
     max_health: i32,
     points: i32,
     drops_health: bool,
-    pub health_bar_entity_index: Option<Index>
+    last_hit_by_player: Option<usize>,
+    pub health_bar_entity: Option<Entity>
 }
 
 impl Killable {
@@ -43,7 +90,8 @@ impl Killable {
             max_health: max_health,
             points: points,
             drops_health: drops_health,
-            health_bar_entity_index: None
+            last_hit_by_player: None,
+            health_bar_entity: None
         };
     }
 
@@ -53,7 +101,8 @@ impl Killable {
             max_health: max_health,
             points: 0,
             drops_health: false,
-            health_bar_entity_index: None
+            last_hit_by_player: None,
+            health_bar_entity: None
         };
     }
 
@@ -61,6 +110,17 @@ impl Killable {
         self.health -= 1;
     }
 
+    pub fn deal_damage_by_player(&mut self, player_index: usize) {
+        self.deal_damage();
+        self.last_hit_by_player = Some(player_index);
+    }
+
+    // Negative amounts never leave less than one health
+    pub fn add_bonus_health(&mut self, amount: i32) {
+        self.max_health = (self.max_health + amount).max(1);
+        self.health = (self.health + amount).max(1).min(self.max_health);
+    }
+
     pub fn gain_health(&mut self) {
         if self.health < self.max_health {
             self.health += 1;
@@ -87,33 +147,91 @@ impl Killable {
         return self.drops_health;
     }
 
+    pub fn get_last_hit_by_player(&self) -> Option<usize> {
+        return self.last_hit_by_player;
+    }
+
 }
 
 impl Component for Killable {
     type Storage = DenseVecStorage<Self>;
 }
 
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum HealthBarMode {
+    // One sprite scaled to the remaining health
+    Fill,
+    // One sprite per health point
+    Segments
+}
+
+// World-space health bar, a child of its Killable with the bar sprites as children
+pub struct HealthBar {
+    pub mode: HealthBarMode,
+    pub inner_width: f32,
+    // Every sprite of the bar, fill entities are a part of them
+    pub sprite_entities: Vec<Entity>,
+    pub fill_entities: Vec<Entity>,
+    pub last_health: i32,
+    pub is_hidden: bool
+}
+
+impl Component for HealthBar {
+    type Storage = DenseVecStorage<Self>;
+}
+
 pub struct SpaceShip {
-    pub is_attacking: bool,
-    pub cannon_entities_indices: Vec<Index> 
+    pub is_attacking: bool
 }
 
 impl Component for SpaceShip {
     type Storage = DenseVecStorage<Self>;
 }
 
+pub struct Player {
+    index: usize
+}
+
+impl Player {
+    pub fn new(index: usize) -> Self {
+        return Self {
+            index
+        };
+    }
+
+    pub fn get_index(&self) -> usize {
+        return self.index;
+    }
+
+    pub fn get_x_axis_binding(&self) -> &'static str {
+        return constants::PLAYER_X_AXIS_BINDINGS[self.index];
+    }
+
+    pub fn get_y_axis_binding(&self) -> &'static str {
+        return constants::PLAYER_Y_AXIS_BINDINGS[self.index];
+    }
+
+    pub fn get_fire_binding(&self) -> &'static str {
+        return constants::PLAYER_FIRE_BINDINGS[self.index];
+    }
+}
+
+impl Component for Player {
+    type Storage = DenseVecStorage<Self>;
+}
+
 pub struct Missile {
-    belongs_to_player: bool
+    player_index: Option<usize>
 }
 
 impl Missile {
-    pub fn new(belongs_to_player: bool) -> Self {
+    pub fn new(player_index: Option<usize>) -> Self {
         return Self {
-            belongs_to_player
+            player_index
         };
     }
-    pub fn belongs_to_player(&self) -> bool {
-        return self.belongs_to_player;
+    pub fn get_player_index(&self) -> Option<usize> {
+        return self.player_index;
     }
 }
 
@@ -129,7 +247,7 @@ pub struct Cannon {
     pub missile_width: f32,
     pub missile_height: f32,
     pub missile_speed: f32,
-    pub missile_sprite_index: usize
+    pub missile_sprite: SpriteRender
 }
 
 impl Component for Cannon {
@@ -156,4 +274,71 @@ impl Expire {
 
 impl Component for Expire {
     type Storage = DenseVecStorage<Self>;
-}
\ No newline at end of file
+}
+
+// Restores one health point every interval seconds
+pub struct Regeneration {
+    interval: f32,
+    elapsed: f32
+}
+
+impl Regeneration {
+
+    pub fn new(interval: f32) -> Self {
+        return Self {
+            interval,
+            elapsed: 0.0
+        };
+    }
+
+    // Returns true when the interval has passed and health should be restored
+    pub fn tick(&mut self, delta_seconds: f32) -> bool {
+        self.elapsed += delta_seconds;
+        if self.elapsed >= self.interval {
+            self.elapsed -= self.interval;
+            return true;
+        }
+        return false;
+    }
+
+}
+
+impl Component for Regeneration {
+    type Storage = DenseVecStorage<Self>;
+}
+
+// Absorbs hits until it runs out of charges
+pub struct Shield {
+    charges: i32
+}
+
+impl Shield {
+
+    pub fn new(charges: i32) -> Self {
+        return Self {
+            charges
+        };
+    }
+
+    pub fn add_charges(&mut self, charges: i32) {
+        self.charges += charges;
+    }
+
+    pub fn get_charges(&self) -> i32 {
+        return self.charges;
+    }
+
+    // Returns true if the hit was absorbed
+    pub fn absorb_hit(&mut self) -> bool {
+        if self.charges > 0 {
+            self.charges -= 1;
+            return true;
+        }
+        return false;
+    }
+
+}
+
+impl Component for Shield {
+    type Storage = DenseVecStorage<Self>;
+}
@@ -43,4 +43,36 @@ pub struct HealthPickupTag;
 
 impl Component for HealthPickupTag {
     type Storage = NullStorage<Self>;
-}
\ No newline at end of file
+}
+
+// UI roots with this tag are fitted inside the arena viewport instead of the whole window
+#[derive(Default)]
+pub struct ViewportAnchorTag;
+
+impl Component for ViewportAnchorTag {
+    type Storage = NullStorage<Self>;
+}
+
+// Set by the console god command, damage is ignored
+#[derive(Default)]
+pub struct InvulnerableTag;
+
+impl Component for InvulnerableTag {
+    type Storage = NullStorage<Self>;
+}
+
+// Campaign boss, it stops near the top of the arena instead of flying through
+#[derive(Default)]
+pub struct BossTag;
+
+impl Component for BossTag {
+    type Storage = NullStorage<Self>;
+}
+
+// Level background drawn behind the arena
+#[derive(Default)]
+pub struct BackgroundTag;
+
+impl Component for BackgroundTag {
+    type Storage = NullStorage<Self>;
+}
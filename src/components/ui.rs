@@ -1,16 +0,0 @@
-use amethyst::{
-    ecs::{
-        Component,
-        DenseVecStorage,
-        world::Index
-    }
-};
-
-pub struct UiKillable {
-    pub last_health: i32,
-    pub health_segment_entities: Vec<Index>
-}
-
-impl Component for UiKillable {
-    type Storage = DenseVecStorage<Self>;
-}
\ No newline at end of file
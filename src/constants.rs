@@ -1,13 +1,46 @@
-pub const ARENA_WIDTH: f32 = 750.0;
-pub const ARENA_HEIGHT: f32 = 900.0;
+pub const CAMPAIGN_SAVE_PATH: &str = "save/campaign.json";
+pub const LETTERBOX_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
+pub const FULLSCREEN_BINDING: &str = "toggle_fullscreen";
+pub const DEBUG_OVERLAY_BINDING: &str = "toggle_debug_overlay";
 
-pub const ENEMY_SPAWNER_MIN_DELAY: f64 = 0.5;
-pub const ENEMY_SPAWNER_MAX_DELAY: f64 = 2.0;
+// Sprites used by the code directly, as (sprite sheet, sprite name)
+pub const HEALTH_PICKUP_SPRITE: (&str, &str) = ("main", "health_pickup");
+pub const HEALTH_BAR_BORDER_SPRITE: (&str, &str) = ("health_bar", "border");
+pub const HEALTH_BAR_FILL_SPRITE: (&str, &str) = ("health_bar", "fill");
+pub const HEALTH_BAR_EMPTY_SPRITE: (&str, &str) = ("health_bar", "empty");
+// Health bar sprites are squares of this size in pixels, scaled to the bar size
+pub const HEALTH_BAR_SPRITE_SIZE: f32 = 2.0;
+// Level backgrounds are sprites of this size in pixels, stretched over the arena
+pub const BACKGROUND_SPRITE_WIDTH: f32 = 150.0;
+pub const BACKGROUND_SPRITE_HEIGHT: f32 = 180.0;
+pub const REQUIRED_SPRITES: [(&str, &str); 4] = [
+    HEALTH_PICKUP_SPRITE,
+    HEALTH_BAR_BORDER_SPRITE,
+    HEALTH_BAR_FILL_SPRITE,
+    HEALTH_BAR_EMPTY_SPRITE
+];
 
+// Menu layouts used by the code, from assets/ui/layouts/menus.ron
+pub const REQUIRED_MENU_LAYOUTS: [&str; 2] = ["default", "ship_select"];
+
+pub const UI_FONT: &str = "default";
+pub const HUD_LIFE_TEXTURE: &str = "life";
+pub const STAT_BAR_BORDER_TEXTURE: &str = "health_bar_border";
+pub const STAT_BAR_FILL_TEXTURE: &str = "health_bar_green";
+// Manifest entries used by the code directly, menus reference theirs through the layouts
+pub const REQUIRED_FONTS: [&str; 1] = [UI_FONT];
+pub const REQUIRED_TEXTURES: [&str; 3] = [HUD_LIFE_TEXTURE, STAT_BAR_BORDER_TEXTURE, STAT_BAR_FILL_TEXTURE];
 pub const UI_FONT_COLOR: [f32; 4] = [0.95, 0.95, 0.95, 1.0];
-pub const UI_BUTTON_WIDTH: f32 = 256.0;
-pub const UI_BUTTON_HEIGHT: f32 = 64.0;
-pub const UI_BUTTON_FONT_SIZE: f32 = 32.0;
-pub const UI_GAMEPLAY_FONT_SIZE: f32 = 18.0;
+pub const UI_ERROR_FONT_COLOR: [f32; 4] = [0.95, 0.3, 0.3, 1.0];
+pub const UI_ERROR_FONT_SIZE: f32 = 14.0;
+pub const UI_DEBUG_FONT_SIZE: f32 = 12.0;
+pub const CONSOLE_BACKGROUND_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 0.8];
+pub const PAUSE_OVERLAY_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 0.6];
+pub const UI_STAT_BAR_WIDTH: f32 = 200.0;
+pub const UI_STAT_BAR_HEIGHT: f32 = 16.0;
 
-pub const PLAYER_LIVES: i32 = 3;
\ No newline at end of file
+pub const MAX_PLAYERS: usize = 2;
+pub const DEFAULT_PLAYER_SHIP: &str = "fighter";
+pub const PLAYER_X_AXIS_BINDINGS: [&str; MAX_PLAYERS] = ["x_axis_p1", "x_axis_p2"];
+pub const PLAYER_Y_AXIS_BINDINGS: [&str; MAX_PLAYERS] = ["y_axis_p1", "y_axis_p2"];
+pub const PLAYER_FIRE_BINDINGS: [&str; MAX_PLAYERS] = ["fire_p1", "fire_p2"];
\ No newline at end of file
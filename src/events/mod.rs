@@ -0,0 +1,58 @@
+use amethyst::ecs::Entity;
+use crate::components::CollisionLayer;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Collision {
+    first: Entity,
+    first_layer: CollisionLayer,
+    second: Entity,
+    second_layer: CollisionLayer
+}
+
+impl Collision {
+
+    pub fn new(
+        first: Entity,
+        first_layer: CollisionLayer,
+        second: Entity,
+        second_layer: CollisionLayer
+    ) -> Self {
+        // Keep pairs in a stable order, so the same collision is always equal to itself
+        return if (first_layer, first) <= (second_layer, second) {
+            Self {
+                first,
+                first_layer,
+                second,
+                second_layer
+            }
+        } else {
+            Self {
+                first: second,
+                first_layer: second_layer,
+                second: first,
+                second_layer: first_layer
+            }
+        };
+    }
+
+    pub fn get_pair(&self) -> (Entity, Entity) {
+        return (self.first, self.second);
+    }
+
+    pub fn get_entities(&self, first_layer: CollisionLayer, second_layer: CollisionLayer) -> Option<(Entity, Entity)> {
+        if self.first_layer == first_layer && self.second_layer == second_layer {
+            return Some((self.first, self.second));
+        } else if self.first_layer == second_layer && self.second_layer == first_layer {
+            return Some((self.second, self.first));
+        }
+
+        return None;
+    }
+
+}
+
+#[derive(Clone, Debug)]
+pub enum CollisionEvent {
+    CollisionStarted(Collision),
+    CollisionEnded(Collision)
+}
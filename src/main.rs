@@ -14,12 +14,16 @@ use amethyst::{
     input::InputBundle,
     prelude::*,
     renderer::{
+        DebugLines,
+        DebugLinesParams,
         DisplayConfig, 
+        DrawDebugLines,
         DrawFlat2D, 
         Pipeline,
         RenderBundle, 
         Stage,
         ColorMask,
+        PosColorNorm,
         ALPHA
     },
     ui::{
@@ -28,6 +32,11 @@ use amethyst::{
     },
     utils::application_root_dir,
 };
+use log::{
+    error,
+    info
+};
+use crate::resources::GameRules;
 
 
 fn main() -> amethyst::Result<()> {
@@ -35,6 +44,14 @@ fn main() -> amethyst::Result<()> {
 
     amethyst::start_logger(Default::default());
 
+    let args: Vec<String> = std::env::args().collect();
+    let is_dev_mode = args.iter().any(|arg| arg == "--dev");
+
+    let game_rules = load_game_rules(&args).unwrap_or_else(|err| {
+        error!("Invalid game rules, {}", err);
+        std::process::exit(1);
+    });
+
     // Display config
     let path = format!(
         "{}/assets/config/display_config.ron",
@@ -49,6 +66,7 @@ fn main() -> amethyst::Result<()> {
                 ALPHA,
                 None
             ))
+            .with_pass(DrawDebugLines::<PosColorNorm>::new())
             .with_pass(DrawUi::new())
     );
     // Input config
@@ -58,7 +76,7 @@ fn main() -> amethyst::Result<()> {
     );
     let input_bundle = InputBundle::<String, String>::new().with_bindings_from_file(binding_path)?;
 
-    let game_data = GameDataBuilder::default()
+    let mut game_data = GameDataBuilder::default()
         .with_bundle(
             RenderBundle::new(
                 pipe, 
@@ -67,10 +85,43 @@ fn main() -> amethyst::Result<()> {
         )?
         .with_bundle(TransformBundle::new())?
         .with_bundle(input_bundle)?
-        .with_bundle(UiBundle::<String, String>::new())?;
-    let mut game = Application::new("./", LoadingState::new(), game_data)?;
+        .with_bundle(UiBundle::<String, String>::new())?
+        .with(systems::ViewportSystem::default(), "viewport", &[])
+        .with(systems::DebugOverlaySystem::default(), "debug_overlay", &["viewport"])
+        .with(systems::ConsoleSystem::default(), "console", &[]);
+    if is_dev_mode {
+        game_data = game_data.with(systems::HotReloadSystem::default(), "hot_reload", &[]);
+    }
+    let mut game = Application::build("./", LoadingState::new())?
+        .with_resource(game_rules)
+        .with_resource(DebugLines::new())
+        .with_resource(DebugLinesParams { line_width: 2.0 })
+        .build(game_data)?;
 
     game.run();
 
     Ok(())
 }
+
+// Rules come from --rules <path> or the default file, then every --rule key=value is applied
+fn load_game_rules(args: &[String]) -> Result<GameRules, String> {
+    let path = args
+        .iter()
+        .position(|arg| arg == "--rules")
+        .and_then(|index| args.get(index + 1).cloned())
+        .unwrap_or_else(|| format!("{}/assets/config/game_rules.ron", application_root_dir()));
+    let mut game_rules = GameRules::load_no_fallback(&path).map_err(|err| format!("{}: {}", path, err))?;
+
+    for (index, arg) in args.iter().enumerate() {
+        if arg == "--rule" {
+            let rule = args
+                .get(index + 1)
+                .ok_or_else(|| String::from("--rule expects key=value"))?;
+            game_rules.apply_override(rule)?;
+            info!("Game rule override: {}", rule);
+        }
+    }
+    game_rules.validate()?;
+
+    return Ok(game_rules);
+}
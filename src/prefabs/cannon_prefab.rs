@@ -0,0 +1,82 @@
+use amethyst::{
+    core::Parent,
+    ecs::{
+        Entities,
+        Entity,
+        WriteStorage
+    }
+};
+use crate::{
+    components::Cannon,
+    prefabs::{
+        FieldErrors,
+        validate_sprite
+    },
+    resources::{
+        SpriteNames,
+        SpriteRef,
+        SpriteSheets
+    }
+};
+use serde::{Serialize, Deserialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct CannonPrefabData {
+    pub x_offset: f32,
+    pub y_offset: f32,
+    pub missile_width: f32,
+    pub missile_height: f32,
+    pub missile_speed: f32,
+    pub missile_sprite: SpriteRef
+}
+
+impl CannonPrefabData {
+
+    pub fn validate(&self, field: &str, sprite_names: &SpriteNames, errors: &mut FieldErrors) {
+        errors.check(self.missile_width > 0.0, &format!("{}.missile_width", field), "must be positive");
+        errors.check(self.missile_height > 0.0, &format!("{}.missile_height", field), "must be positive");
+        errors.check(self.missile_speed > 0.0, &format!("{}.missile_speed", field), "must be positive");
+        validate_sprite(&self.missile_sprite, &format!("{}.missile_sprite", field), sprite_names, errors);
+    }
+
+    pub fn init(&mut self, scale: f32) {
+        self.x_offset = self.x_offset * scale;
+        self.y_offset = self.y_offset * scale;
+        self.missile_width = self.missile_width * scale;
+        self.missile_height = self.missile_height * scale;
+    }
+
+    pub fn create_entity<'a>(
+        &self,
+        entities: &Entities,
+        space_ship_entity: Entity,
+        attack_cooldown: f64,
+        sprite_sheets: &SpriteSheets,
+        cannons: &mut WriteStorage<'a, Cannon>,
+        parents: &mut WriteStorage<'a, Parent>
+    ) -> Entity {
+        let cannon_entity = entities.create();
+        cannons
+            .insert(cannon_entity, Cannon {
+                x_offset: self.x_offset,
+                y_offset: self.y_offset,
+                attack_cooldown: attack_cooldown,
+                last_attack_time: 0.0,
+                missile_width: self.missile_width,
+                missile_height: self.missile_height,
+                missile_speed: self.missile_speed,
+                missile_sprite: sprite_sheets
+                    .get_sprite_render(&self.missile_sprite)
+                    .expect("Missile sprite should have been validated when loading the prefab!")
+            })
+            .expect("Could not create Cannon!");
+        parents
+            .insert(cannon_entity, Parent {
+                entity: space_ship_entity
+            })
+            .expect("Could not create Parent!");
+
+        return cannon_entity;
+    }
+
+}
@@ -6,44 +6,58 @@ use amethyst::{
     },
     ecs::{
         Entities,
+        Entity,
         ReadExpect,
         WriteStorage
     },
     renderer::{
-        SpriteRender,
-        SpriteSheetHandle
+        Hidden,
+        SpriteRender
     }
 };
 use crate::{
     components::{
         Cannon,
+        CollisionLayer,
+        HealthBar,
         Killable,
         Moveable,
         Rect,
+        RigidBody,
         SpaceShip,
+        hitbox::{
+            Hitbox,
+            HitboxShape
+        },
         tags::{
             DestroyOutOfArenaTag,
             EnemyTag
         }
     },
-    prefabs::SimplePrefab
+    prefabs::{
+        CannonPrefabData,
+        FieldErrors,
+        HealthBarPrefabData,
+        SimplePrefab,
+        validate_hitbox,
+        validate_sprite
+    },
+    resources::{
+        SpriteNames,
+        SpriteRef,
+        SpriteSheets
+    }
 };
 use rand::prelude::*;
 use serde::{Serialize, Deserialize};
-
-#[derive(Serialize, Deserialize)]
-pub struct CannonPrefabData {
-    pub x_offset: f32,
-    pub y_offset: f32,
-    pub missile_width: f32,
-    pub missile_height: f32,
-    pub missile_speed: f32,
-    pub missile_sprite_index: usize
-}
+use shred_derive::SystemData;
 
 #[derive(Serialize, Deserialize)]
 pub struct EnemyPrefabData {
-    pub sprite_index: usize,
+    pub name: String,
+    pub category: String,
+    pub spawn_weight: f64,
+    pub sprite: SpriteRef,
     pub movement_speed_min: f32,
     pub movement_speed_max: f32,
     pub width: f32,
@@ -53,23 +67,92 @@ pub struct EnemyPrefabData {
     pub points: i32,
     pub drops_health: bool,
     pub attack_cooldown: Option<f64>,
-    pub cannon_prefabs: Option<Vec<CannonPrefabData>>
+    pub cannon_prefabs: Option<Vec<CannonPrefabData>>,
+    pub hitbox: Option<Vec<HitboxShape>>,
+    pub mass: Option<f32>,
+    pub health_bar: Option<HealthBarPrefabData>
+}
+
+impl EnemyPrefabData {
+
+    pub fn validate(&self, sprite_names: &SpriteNames) -> FieldErrors {
+        let mut errors = FieldErrors::new();
+
+        errors.check(!self.name.is_empty(), "name", "must not be empty");
+        errors.check(!self.category.is_empty(), "category", "must not be empty");
+        errors.check(self.spawn_weight >= 0.0, "spawn_weight", "must not be negative");
+        validate_sprite(&self.sprite, "sprite", sprite_names, &mut errors);
+        errors.check(self.movement_speed_min >= 0.0, "movement_speed_min", "must not be negative");
+        errors.check(
+            self.movement_speed_min <= self.movement_speed_max,
+            "movement_speed_min",
+            "must not be greater than movement_speed_max"
+        );
+        errors.check(self.width > 0.0, "width", "must be positive");
+        errors.check(self.height > 0.0, "height", "must be positive");
+        errors.check(self.scale > 0.0, "scale", "must be positive");
+        errors.check(self.health > 0, "health", "must be positive");
+        errors.check(self.points >= 0, "points", "must not be negative");
+        if let Some(mass) = self.mass {
+            errors.check(mass > 0.0, "mass", "must be positive");
+        }
+
+        match (&self.cannon_prefabs, self.attack_cooldown) {
+            (Some(cannon_prefabs), attack_cooldown) => {
+                errors.check(attack_cooldown.is_some(), "attack_cooldown", "is required when cannon_prefabs are specified");
+                errors.check(attack_cooldown.unwrap_or(1.0) > 0.0, "attack_cooldown", "must be positive");
+                for (i, cannon_prefab) in cannon_prefabs.iter().enumerate() {
+                    cannon_prefab.validate(&format!("cannon_prefabs[{}]", i), sprite_names, &mut errors);
+                }
+            },
+            (None, Some(_)) => errors.check(false, "attack_cooldown", "has no effect without cannon_prefabs"),
+            (None, None) => {}
+        }
+
+        if let Some(hitbox) = &self.hitbox {
+            validate_hitbox(hitbox, &mut errors);
+        }
+
+        if let Some(health_bar) = &self.health_bar {
+            health_bar.validate("health_bar", self.width * self.scale, self.health, &mut errors);
+        }
+
+        return errors;
+    }
+
+    // Equal bounds give a fixed speed, gen_range needs a non empty range
+    pub fn get_movement_speed<R: Rng>(&self, rng: &mut R) -> f32 {
+        if self.movement_speed_min >= self.movement_speed_max {
+            return self.movement_speed_min;
+        }
+        return rng.gen_range(self.movement_speed_min, self.movement_speed_max);
+    }
+
+}
+
+// Named so systems spawning enemies can use the same storages without relying on their order
+#[derive(SystemData)]
+pub struct EnemyPrefabSystemData<'a> {
+    pub transforms: WriteStorage<'a, Transform>,
+    pub rects: WriteStorage<'a, Rect>,
+    pub moveables: WriteStorage<'a, Moveable>,
+    pub killables: WriteStorage<'a, Killable>,
+    pub space_ships: WriteStorage<'a, SpaceShip>,
+    pub cannons: WriteStorage<'a, Cannon>,
+    pub enemy_tags: WriteStorage<'a, EnemyTag>,
+    pub sprite_renders: WriteStorage<'a, SpriteRender>,
+    pub destroy_out_of_arena_tags: WriteStorage<'a, DestroyOutOfArenaTag>,
+    pub parents: WriteStorage<'a, Parent>,
+    pub collision_layers: WriteStorage<'a, CollisionLayer>,
+    pub hitboxes: WriteStorage<'a, Hitbox>,
+    pub rigid_bodies: WriteStorage<'a, RigidBody>,
+    pub sprite_sheets: ReadExpect<'a, SpriteSheets>,
+    pub hiddens: WriteStorage<'a, Hidden>,
+    pub health_bars: WriteStorage<'a, HealthBar>
 }
 
 impl<'a> SimplePrefab<'a> for EnemyPrefabData {
-    type SystemData = (
-        WriteStorage<'a, Transform>,
-        WriteStorage<'a, Rect>,
-        WriteStorage<'a, Moveable>,
-        WriteStorage<'a, Killable>,
-        WriteStorage<'a, SpaceShip>,
-        WriteStorage<'a, Cannon>,
-        WriteStorage<'a, EnemyTag>,
-        WriteStorage<'a, SpriteRender>,
-        WriteStorage<'a, DestroyOutOfArenaTag>,
-        WriteStorage<'a, Parent>,
-        ReadExpect<'a, SpriteSheetHandle>
-    );
+    type SystemData = EnemyPrefabSystemData<'a>;
 
     fn init(&mut self) {
         // Apply scale to all dimensions
@@ -78,12 +161,16 @@ impl<'a> SimplePrefab<'a> for EnemyPrefabData {
 
         if let Some(cannon_prefabs) = &mut self.cannon_prefabs {
             for cannon in cannon_prefabs {
-                cannon.x_offset = cannon.x_offset * self.scale;
-                cannon.y_offset = cannon.y_offset * self.scale;
-                cannon.missile_width = cannon.missile_width * self.scale;
-                cannon.missile_height = cannon.missile_height * self.scale;
+                cannon.init(self.scale);
             }
         }
+
+        if let Some(hitbox) = &mut self.hitbox {
+            *hitbox = hitbox
+                .iter()
+                .map(|shape| shape.scaled(self.scale))
+                .collect();
+        }
     }
 
     fn create_entity(
@@ -91,7 +178,7 @@ impl<'a> SimplePrefab<'a> for EnemyPrefabData {
         entities: &'a Entities, 
         x: f32,
         y: f32,
-        (
+        EnemyPrefabSystemData {
             ref mut transforms,
             ref mut rects,
             ref mut moveables,
@@ -102,9 +189,14 @@ impl<'a> SimplePrefab<'a> for EnemyPrefabData {
             ref mut sprite_renders,
             ref mut destroy_out_of_arena_tags,
             ref mut parents,
-            sprite_sheet_handle
-        ): &mut Self::SystemData
-    ) {
+            ref mut collision_layers,
+            ref mut hitboxes,
+            ref mut rigid_bodies,
+            sprite_sheets,
+            ref mut hiddens,
+            ref mut health_bars
+        }: &mut Self::SystemData
+    ) -> Entity {
         let enemy_entity = entities.create();
 
         let mut transform = Transform::default();
@@ -122,7 +214,7 @@ impl<'a> SimplePrefab<'a> for EnemyPrefabData {
             .expect("Could not create Rect!");
         moveables
             .insert(enemy_entity, Moveable {
-                move_speed: rand::thread_rng().gen_range(self.movement_speed_min, self.movement_speed_max),
+                move_speed: self.get_movement_speed(&mut rand::thread_rng()),
                 direction: Vector2::new(0.0, -1.0)
             })
             .expect("Could not create Moveable!");
@@ -135,47 +227,114 @@ impl<'a> SimplePrefab<'a> for EnemyPrefabData {
         destroy_out_of_arena_tags
             .insert(enemy_entity, DestroyOutOfArenaTag)
             .expect("Could not create DestroyOutOfArenaTag!");
+        collision_layers
+            .insert(enemy_entity, CollisionLayer::Enemy)
+            .expect("Could not create CollisionLayer!");
+        rigid_bodies
+            .insert(enemy_entity, RigidBody::new(self.mass.unwrap_or(1.0)))
+            .expect("Could not create RigidBody!");
+        if let Some(hitbox) = &self.hitbox {
+            hitboxes
+                .insert(enemy_entity, Hitbox::new(hitbox.clone()))
+                .expect("Could not create Hitbox!");
+        }
+        let sprite_render = sprite_sheets
+            .get_sprite_render(&self.sprite)
+            .expect("Sprite should have been validated when loading the prefab!");
         sprite_renders
-            .insert(enemy_entity, SpriteRender {
-                sprite_sheet: sprite_sheet_handle.clone(),
-                sprite_number: self.sprite_index
-            })
+            .insert(enemy_entity, sprite_render)
             .expect("Could not create SpriteRender!");
         space_ships
             .insert(enemy_entity, SpaceShip {
-                is_attacking: true,
-                cannon_entities_indices: Vec::new()
+                is_attacking: true
             })
             .expect("Could not create SpaceShip!");
 
-        let enemy_space_ship = space_ships.get_mut(enemy_entity).unwrap();
-        // Create cannons
+        // Create cannons, they are children of the ship and get deleted with it
         if let Some(cannon_prefabs) = &self.cannon_prefabs {
             let attack_cooldown = self.attack_cooldown
                 .expect("Attack cooldown is required if cannons are specified!");
 
             for cannon_prefab in cannon_prefabs {
-                let cannon_entity = entities.create();
-                enemy_space_ship.cannon_entities_indices.push(cannon_entity.id());
-                cannons
-                    .insert(cannon_entity, Cannon {
-                        x_offset: cannon_prefab.x_offset,
-                        y_offset: cannon_prefab.y_offset,
-                        attack_cooldown: attack_cooldown,
-                        last_attack_time: 0.0,
-                        missile_width: cannon_prefab.missile_width,
-                        missile_height: cannon_prefab.missile_height,
-                        missile_speed: cannon_prefab.missile_speed,
-                        missile_sprite_index: cannon_prefab.missile_sprite_index
-                    })
-                    .expect("Could not create Cannon!");
-                parents
-                    .insert(cannon_entity, Parent {
-                        entity: enemy_entity
-                    })
-                    .expect("Could not create Parent!");
+                cannon_prefab.create_entity(entities, enemy_entity, attack_cooldown, sprite_sheets, cannons, parents);
             }
         }
+
+        // Health bar is a child as well, prefabs without configuration get the default one
+        let default_health_bar = HealthBarPrefabData::default();
+        let health_bar_entity = self.health_bar
+            .as_ref()
+            .unwrap_or(&default_health_bar)
+            .create_entity(
+                entities,
+                enemy_entity,
+                self.width,
+                self.height,
+                self.scale,
+                self.health,
+                sprite_sheets,
+                transforms,
+                parents,
+                sprite_renders,
+                hiddens,
+                health_bars
+            );
+        killables.get_mut(enemy_entity).unwrap().health_bar_entity = Some(health_bar_entity);
+
+        return enemy_entity;
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        rngs::StdRng,
+        SeedableRng
+    };
+    use super::*;
+
+    fn create_prefab(movement_speed_min: f32, movement_speed_max: f32) -> EnemyPrefabData {
+        let source = format!(
+            r#"{{
+                "name": "test",
+                "category": "small",
+                "spawn_weight": 1.0,
+                "sprite": {{"sheet": "main", "name": "test"}},
+                "movement_speed_min": {},
+                "movement_speed_max": {},
+                "width": 10.0,
+                "height": 10.0,
+                "scale": 1.0,
+                "health": 1,
+                "points": 1,
+                "drops_health": false
+            }}"#,
+            movement_speed_min,
+            movement_speed_max
+        );
+        return serde_json::from_str(&source).expect("Test prefab should parse!");
+    }
+
+    #[test]
+    fn equal_movement_speeds_give_a_fixed_speed() {
+        let prefab = create_prefab(30.0, 30.0);
+        let mut rng = StdRng::seed_from_u64(31);
+
+        for _ in 0..10 {
+            assert_eq!(prefab.get_movement_speed(&mut rng), 30.0);
+        }
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn movement_speed_stays_within_range() {
+        let prefab = create_prefab(10.0, 50.0);
+        let mut rng = StdRng::seed_from_u64(31);
+
+        for _ in 0..100 {
+            let speed = prefab.get_movement_speed(&mut rng);
+            assert!(speed >= 10.0 && speed < 50.0);
+        }
+    }
+
+}
@@ -0,0 +1,164 @@
+use amethyst::{
+    core::{
+        Parent,
+        Transform
+    },
+    ecs::{
+        Entities,
+        Entity,
+        WriteStorage
+    },
+    renderer::{
+        Hidden,
+        SpriteRender
+    }
+};
+use crate::{
+    components::{
+        HealthBar,
+        HealthBarMode
+    },
+    constants,
+    prefabs::FieldErrors,
+    resources::{
+        SpriteRef,
+        SpriteSheets
+    }
+};
+use serde::{Serialize, Deserialize};
+
+// Border around the bar and gap between segments, in world units
+const BORDER: f32 = 1.0;
+
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct HealthBarPrefabData {
+    pub mode: HealthBarMode,
+    pub height: f32,
+    pub y_offset: f32,
+    pub hidden_until_damaged: bool
+}
+
+impl Default for HealthBarPrefabData {
+
+    fn default() -> Self {
+        return Self {
+            mode: HealthBarMode::Fill,
+            height: 10.0,
+            y_offset: 10.0,
+            hidden_until_damaged: true
+        };
+    }
+
+}
+
+impl HealthBarPrefabData {
+
+    // Owner width in world units, segments need at least a unit of width each
+    pub fn validate(&self, field: &str, owner_width: f32, max_health: i32, errors: &mut FieldErrors) {
+        errors.check(self.height > 2.0 * BORDER, &format!("{}.height", field), &format!("must be greater than {}", 2.0 * BORDER));
+        if self.mode == HealthBarMode::Segments {
+            let segment_width = (owner_width - BORDER * (max_health + 1) as f32) / max_health as f32;
+            errors.check(segment_width >= 1.0, &format!("{}.mode", field), "Segments is too narrow for this much health, use Fill");
+        }
+    }
+
+    // The bar is placed above the owner, all local values are divided by the owner scale
+    // because children inherit it
+    pub fn create_entity<'a>(
+        &self,
+        entities: &Entities,
+        owner_entity: Entity,
+        owner_width: f32,
+        owner_height: f32,
+        owner_scale: f32,
+        max_health: i32,
+        sprite_sheets: &SpriteSheets,
+        transforms: &mut WriteStorage<'a, Transform>,
+        parents: &mut WriteStorage<'a, Parent>,
+        sprite_renders: &mut WriteStorage<'a, SpriteRender>,
+        hiddens: &mut WriteStorage<'a, Hidden>,
+        health_bars: &mut WriteStorage<'a, HealthBar>
+    ) -> Entity {
+        let width = owner_width / owner_scale;
+        let height = self.height / owner_scale;
+        let border = BORDER / owner_scale;
+        let inner_width = width - 2.0 * border;
+        let inner_height = height - 2.0 * border;
+
+        let health_bar_entity = entities.create();
+        let mut transform = Transform::default();
+        transform.set_xyz(0.0, (owner_height / 2.0 + self.y_offset + self.height / 2.0) / owner_scale, 0.1);
+        transforms
+            .insert(health_bar_entity, transform)
+            .expect("Could not create Transform!");
+        parents
+            .insert(health_bar_entity, Parent {
+                entity: owner_entity
+            })
+            .expect("Could not create Parent!");
+
+        let mut sprite_entities = Vec::new();
+        let mut create_sprite = |sprite: (&str, &str), x: f32, z: f32, sprite_width: f32, sprite_height: f32| {
+            let sprite_entity = entities.create();
+            let mut transform = Transform::default();
+            transform.set_xyz(x, 0.0, z);
+            transform.set_scale(
+                sprite_width / constants::HEALTH_BAR_SPRITE_SIZE,
+                sprite_height / constants::HEALTH_BAR_SPRITE_SIZE,
+                1.0
+            );
+            transforms
+                .insert(sprite_entity, transform)
+                .expect("Could not create Transform!");
+            parents
+                .insert(sprite_entity, Parent {
+                    entity: health_bar_entity
+                })
+                .expect("Could not create Parent!");
+            sprite_renders
+                .insert(sprite_entity, sprite_sheets
+                    .get_sprite_render(&SpriteRef::new(sprite.0, sprite.1))
+                    .expect("Health bar sprites should have been validated while loading!"))
+                .expect("Could not create SpriteRender!");
+            if self.hidden_until_damaged {
+                hiddens
+                    .insert(sprite_entity, Hidden)
+                    .expect("Could not create Hidden!");
+            }
+            sprite_entities.push(sprite_entity);
+            return sprite_entity;
+        };
+
+        create_sprite(constants::HEALTH_BAR_BORDER_SPRITE, 0.0, 0.0, width, height);
+        let fill_entities = match self.mode {
+            HealthBarMode::Fill => {
+                create_sprite(constants::HEALTH_BAR_EMPTY_SPRITE, 0.0, 0.01, inner_width, inner_height);
+                vec![create_sprite(constants::HEALTH_BAR_FILL_SPRITE, 0.0, 0.02, inner_width, inner_height)]
+            },
+            HealthBarMode::Segments => {
+                let segment_width = (inner_width - border * (max_health - 1) as f32) / max_health as f32;
+                (0..max_health)
+                    .map(|i| {
+                        let x = -inner_width / 2.0 + segment_width / 2.0 + (segment_width + border) * i as f32;
+                        create_sprite(constants::HEALTH_BAR_FILL_SPRITE, x, 0.01, segment_width, inner_height)
+                    })
+                    .collect()
+            }
+        };
+
+        health_bars
+            .insert(health_bar_entity, HealthBar {
+                mode: self.mode,
+                inner_width,
+                sprite_entities,
+                fill_entities,
+                last_health: max_health,
+                is_hidden: self.hidden_until_damaged
+            })
+            .expect("Could not create HealthBar!");
+
+        return health_bar_entity;
+    }
+
+}
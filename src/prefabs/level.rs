@@ -0,0 +1,93 @@
+use serde::{Serialize, Deserialize};
+use crate::{
+    prefabs::{
+        EnemyPrefabs,
+        FieldErrors,
+        validate_sprite
+    },
+    resources::{
+        SpriteNames,
+        SpriteRef
+    }
+};
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum CompletionCondition {
+    // Every wave spawned and no enemy left, including the boss
+    ClearWaves,
+    DefeatBoss,
+    Survive { seconds: f32 },
+    ReachScore { points: i32 }
+}
+
+// Enemies of a single prefab spawned one after another, x is a fraction of the arena width and
+// random when not set
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WaveSpawnData {
+    pub prefab: String,
+    pub count: u32,
+    pub interval: f32,
+    pub x: Option<f32>
+}
+
+// Waves start delay seconds after the previous wave is done spawning, spawns of a wave run at
+// the same time
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WaveData {
+    pub delay: f32,
+    pub spawns: Vec<WaveSpawnData>
+}
+
+// Single campaign level, the boss spawns once every wave is done and the arena is clear
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LevelData {
+    pub name: String,
+    pub background: Option<SpriteRef>,
+    pub waves: Vec<WaveData>,
+    pub boss: Option<String>,
+    pub completion: CompletionCondition
+}
+
+impl LevelData {
+
+    pub fn validate(&self, sprite_names: &SpriteNames, enemy_prefabs: &EnemyPrefabs) -> FieldErrors {
+        let mut errors = FieldErrors::new();
+
+        errors.check(!self.name.is_empty(), "name", "must not be empty");
+        if let Some(background) = &self.background {
+            validate_sprite(background, "background", sprite_names, &mut errors);
+        }
+
+        for (i, wave) in self.waves.iter().enumerate() {
+            let field = format!("waves[{}]", i);
+            errors.check(wave.delay >= 0.0, &format!("{}.delay", field), "must not be negative");
+            errors.check(!wave.spawns.is_empty(), &format!("{}.spawns", field), "must not be empty");
+            for (j, spawn) in wave.spawns.iter().enumerate() {
+                let field = format!("{}.spawns[{}]", field, j);
+                errors.check(enemy_prefabs.contains(&spawn.prefab), &format!("{}.prefab", field), "is not a loaded enemy prefab");
+                errors.check(spawn.count > 0, &format!("{}.count", field), "must be positive");
+                errors.check(spawn.interval >= 0.0, &format!("{}.interval", field), "must not be negative");
+                if let Some(x) = spawn.x {
+                    errors.check(x >= 0.0 && x <= 1.0, &format!("{}.x", field), "must be between 0.0 and 1.0");
+                }
+            }
+        }
+
+        if let Some(boss) = &self.boss {
+            errors.check(enemy_prefabs.contains(boss), "boss", "is not a loaded enemy prefab");
+        }
+
+        match self.completion {
+            CompletionCondition::ClearWaves => {
+                errors.check(!self.waves.is_empty() || self.boss.is_some(), "completion", "ClearWaves needs waves or a boss");
+            },
+            CompletionCondition::DefeatBoss => errors.check(self.boss.is_some(), "completion", "DefeatBoss needs a boss"),
+            CompletionCondition::Survive { seconds } => errors.check(seconds > 0.0, "completion.seconds", "must be positive"),
+            CompletionCondition::ReachScore { points } => errors.check(points > 0, "completion.points", "must be positive")
+        }
+
+        return errors;
+    }
+
+}
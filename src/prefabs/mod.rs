@@ -1,34 +1,155 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{
+        Path,
+        PathBuf
+    }
+};
 use amethyst::{
     ecs::{
         Entities,
+        Entity,
         SystemData
     }
 };
+use log::warn;
+use rand::Rng;
+use serde::de::DeserializeOwned;
+use crate::{
+    constants,
+    resources::SpriteNames
+};
 
+mod cannon_prefab;
 mod enemy_prefab;
+mod health_bar_prefab;
+mod level;
+mod player_prefab;
+mod validation;
 
 pub use {
-    enemy_prefab::EnemyPrefabData
+    cannon_prefab::CannonPrefabData,
+    enemy_prefab::{
+        EnemyPrefabData,
+        EnemyPrefabSystemData
+    },
+    health_bar_prefab::HealthBarPrefabData,
+    level::{
+        CompletionCondition,
+        LevelData
+    },
+    player_prefab::{
+        PlayerShipPrefabData,
+        SpecialAbility
+    },
+    validation::{
+        FieldErrors,
+        PrefabError,
+        load_sprite_name_map,
+        validate_hitbox,
+        validate_sprite
+    }
 };
 
+#[derive(Default)]
 pub struct EnemyPrefabs {
-    pub small_enemy_prefabs: Vec<EnemyPrefabData>,
-    pub medium_enemy_prefabs: Vec<EnemyPrefabData>,
-    pub large_enemy_prefabs: Vec<EnemyPrefabData>
+    prefabs: HashMap<String, EnemyPrefabData>
 }
 
 impl EnemyPrefabs {
 
-    pub fn small_enemy_count(&self) -> usize {
-        return self.small_enemy_prefabs.len();
+    pub fn get(&self, name: &str) -> Option<&EnemyPrefabData> {
+        return self.prefabs.get(name);
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        return self.prefabs.contains_key(name);
+    }
+
+    pub fn insert(&mut self, prefab: EnemyPrefabData) {
+        self.prefabs.insert(prefab.name.clone(), prefab);
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.prefabs.keys().cloned().collect();
+        names.sort();
+        return names;
+    }
+
+    // Picks a category by its weight, then one of its prefabs by spawn weight. Categories
+    // without a weight or without spawnable prefabs are skipped.
+    pub fn pick_random<R: Rng>(&self, category_weights: &HashMap<String, f64>, rng: &mut R) -> Option<&EnemyPrefabData> {
+        let categories = self.get_spawnable_categories(category_weights);
+        let (category, _) = pick_weighted(&categories, |(_, weight)| *weight, rng)?;
+
+        let prefabs: Vec<&EnemyPrefabData> = self.prefabs
+            .values()
+            .filter(|prefab| prefab.category == **category)
+            .collect();
+        return pick_weighted(&prefabs, |prefab| prefab.spawn_weight, rng).cloned();
+    }
+
+    // The endless spawner needs at least one of these to spawn anything
+    pub fn has_spawnable_category(&self, category_weights: &HashMap<String, f64>) -> bool {
+        return !self.get_spawnable_categories(category_weights).is_empty();
+    }
+
+    fn get_spawnable_categories<'a>(&self, category_weights: &'a HashMap<String, f64>) -> Vec<(&'a String, f64)> {
+        return category_weights
+            .iter()
+            .filter(|(category, weight)| **weight > 0.0 && self.prefabs
+                .values()
+                .any(|prefab| prefab.category == **category && prefab.spawn_weight > 0.0)
+            )
+            .map(|(category, weight)| (category, *weight))
+            .collect();
+    }
+
+}
+
+#[derive(Default)]
+pub struct PlayerShipPrefabs {
+    prefabs: HashMap<String, PlayerShipPrefabData>
+}
+
+impl PlayerShipPrefabs {
+
+    pub fn get(&self, name: &str) -> Option<&PlayerShipPrefabData> {
+        return self.prefabs.get(name);
     }
 
-    pub fn medium_enemy_count(&self) -> usize {
-        return self.medium_enemy_prefabs.len();
+    pub fn contains(&self, name: &str) -> bool {
+        return self.prefabs.contains_key(name);
     }
 
-    pub fn large_enemy_count(&self) -> usize {
-        return self.large_enemy_prefabs.len();
+    pub fn insert(&mut self, prefab: PlayerShipPrefabData) {
+        self.prefabs.insert(prefab.name.clone(), prefab);
+    }
+
+    // Ship names sorted alphabetically, so the order is stable between runs
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.prefabs.keys().cloned().collect();
+        names.sort();
+        return names;
+    }
+
+}
+
+// Campaign levels in the order they are played
+#[derive(Default)]
+pub struct Campaign {
+    levels: Vec<LevelData>
+}
+
+impl Campaign {
+
+    pub fn get_level(&self, index: usize) -> Option<&LevelData> {
+        return self.levels.get(index);
+    }
+
+    pub fn get_levels(&self) -> &[LevelData] {
+        return &self.levels;
     }
 
 }
@@ -46,6 +167,191 @@ pub trait SimplePrefab<'a> {
         x: f32, 
         y: f32, 
         system_data: &mut Self::SystemData
-    );
+    ) -> Entity;
+
+}
+
+// Picks an item with chance proportional to its weight
+fn pick_weighted<'a, T, R: Rng>(items: &'a [T], get_weight: impl Fn(&T) -> f64, rng: &mut R) -> Option<&'a T> {
+    let total_weight: f64 = items.iter().map(&get_weight).sum();
+    if total_weight <= 0.0 {
+        return None;
+    }
+
+    let mut remaining_weight = rng.gen_range(0.0, total_weight);
+    for item in items {
+        if remaining_weight < get_weight(item) {
+            return Some(item);
+        }
+        remaining_weight -= get_weight(item);
+    }
+
+    // Floating point leftovers, fall back to the last item with any weight
+    return items
+        .iter()
+        .filter(|item| get_weight(item) > 0.0)
+        .last();
+}
+
+// Reads, parses and validates single enemy prefab file, the returned prefab is already initialised
+pub fn load_enemy_prefab(path: &Path, sprite_names: &SpriteNames) -> Result<EnemyPrefabData, Vec<PrefabError>> {
+    let mut prefab = load_prefab_file(path, |prefab: &EnemyPrefabData| prefab.validate(sprite_names))?;
+    prefab.init();
+    return Ok(prefab);
+}
+
+// Reads, parses and validates single player ship prefab file, the returned prefab is already initialised
+pub fn load_player_ship_prefab(path: &Path, sprite_names: &SpriteNames) -> Result<PlayerShipPrefabData, Vec<PrefabError>> {
+    let mut prefab = load_prefab_file(path, |prefab: &PlayerShipPrefabData| prefab.validate(sprite_names))?;
+    prefab.init();
+    return Ok(prefab);
+}
+
+fn load_prefab_file<T, F>(path: &Path, validate: F) -> Result<T, Vec<PrefabError>>
+where
+    T: DeserializeOwned,
+    F: FnOnce(&T) -> FieldErrors
+{
+    let file_name = path
+        .file_name()
+        .map(|file_name| file_name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string());
+
+    let source = fs::read_to_string(path)
+        .map_err(|err| vec![PrefabError::new(&file_name, format!("could not read file: {}", err))])?;
+    let prefab: T = serde_json::from_str(&source)
+        .map_err(|err| vec![PrefabError::with_line(&file_name, err.line(), err.to_string())])?;
+
+    let errors = validate(&prefab).into_prefab_errors(&file_name, &source);
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    return Ok(prefab);
+}
+
+// Loads every enemy prefab of the prefab directory, failed files are added to errors
+pub fn load_enemy_prefabs(sprite_names: &SpriteNames, errors: &mut Vec<PrefabError>) -> EnemyPrefabs {
+    let mut enemy_prefabs = EnemyPrefabs::default();
+    let paths = match fs::read_dir("assets/prefabs/enemies") {
+        Ok(paths) => paths,
+        Err(err) => {
+            errors.push(PrefabError::new("assets/prefabs/enemies", format!("could not read directory: {}", err)));
+            return enemy_prefabs;
+        }
+    };
+
+    // Editors leave swap and backup files next to the prefabs
+    let paths = paths
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().map_or(false, |extension| extension == "json"));
+    for path in paths {
+        let file_name = path.file_name().unwrap().to_string_lossy().into_owned();
+        match load_enemy_prefab(&path, sprite_names) {
+            Ok(prefab) => {
+                if enemy_prefabs.contains(&prefab.name) {
+                    errors.push(PrefabError::new(&file_name, format!("name \"{}\" is already used by another prefab", prefab.name)));
+                } else {
+                    enemy_prefabs.insert(prefab);
+                }
+            },
+            Err(prefab_errors) => errors.extend(prefab_errors)
+        }
+    }
+
+    return enemy_prefabs;
+}
+
+// Loads every player ship prefab of the prefab directory, failed files are added to errors
+pub fn load_player_ship_prefabs(sprite_names: &SpriteNames, errors: &mut Vec<PrefabError>) -> PlayerShipPrefabs {
+    let mut player_ship_prefabs = PlayerShipPrefabs::default();
+    let paths = match fs::read_dir("assets/prefabs/player") {
+        Ok(paths) => paths,
+        Err(err) => {
+            errors.push(PrefabError::new("assets/prefabs/player", format!("could not read directory: {}", err)));
+            return player_ship_prefabs;
+        }
+    };
+
+    for path in paths.filter_map(|entry| entry.ok()).map(|entry| entry.path()) {
+        let file_name = path.file_name().unwrap().to_string_lossy().into_owned();
+        match load_player_ship_prefab(&path, sprite_names) {
+            Ok(prefab) => {
+                if player_ship_prefabs.contains(&prefab.name) {
+                    errors.push(PrefabError::new(&file_name, format!("name \"{}\" is already used by another prefab", prefab.name)));
+                } else {
+                    player_ship_prefabs.insert(prefab);
+                }
+            },
+            Err(prefab_errors) => errors.extend(prefab_errors)
+        }
+    }
+
+    if !player_ship_prefabs.contains(constants::DEFAULT_PLAYER_SHIP) {
+        errors.push(PrefabError::new(
+            "assets/prefabs/player",
+            format!("default player ship \"{}\" is missing", constants::DEFAULT_PLAYER_SHIP)
+        ));
+    }
+
+    return player_ship_prefabs;
+}
 
-}
\ No newline at end of file
+// Loads the campaign levels, ordered by file name, failed files are added to errors
+pub fn load_campaign(sprite_names: &SpriteNames, enemy_prefabs: &EnemyPrefabs, errors: &mut Vec<PrefabError>) -> Campaign {
+    let mut campaign = Campaign::default();
+    let mut paths: Vec<PathBuf> = match fs::read_dir("assets/campaign") {
+        Ok(paths) => paths.filter_map(|entry| entry.ok()).map(|entry| entry.path()).collect(),
+        Err(err) => {
+            errors.push(PrefabError::new("assets/campaign", format!("could not read directory: {}", err)));
+            return campaign;
+        }
+    };
+    paths.sort();
+
+    for path in paths {
+        match load_prefab_file(&path, |level: &LevelData| level.validate(sprite_names, enemy_prefabs)) {
+            Ok(level) => campaign.levels.push(level),
+            Err(level_errors) => errors.extend(level_errors)
+        }
+    }
+
+    return campaign;
+}
+
+// Loads the enemy prefab directory again with the startup checks and swaps the prefabs only
+// when everything is valid, so a running game never loses a prefab that is still in use
+pub fn reload_enemy_prefabs(
+    enemy_prefabs: &mut EnemyPrefabs,
+    sprite_names: &SpriteNames,
+    category_weights: &HashMap<String, f64>
+) -> Result<(), Vec<PrefabError>> {
+    let mut errors = Vec::new();
+    let loaded_enemy_prefabs = load_enemy_prefabs(sprite_names, &mut errors);
+    check_spawnable_category(&loaded_enemy_prefabs, category_weights, &mut errors);
+    // Campaign levels must still find their prefabs, a renamed or deleted one would stop the level
+    load_campaign(sprite_names, &loaded_enemy_prefabs, &mut errors);
+    if !errors.is_empty() {
+        for error in &errors {
+            warn!("Could not reload enemy prefabs, keeping previous versions: {}", error);
+        }
+        return Err(errors);
+    }
+
+    *enemy_prefabs = loaded_enemy_prefabs;
+    return Ok(());
+}
+
+pub fn check_spawnable_category(
+    enemy_prefabs: &EnemyPrefabs,
+    category_weights: &HashMap<String, f64>,
+    errors: &mut Vec<PrefabError>
+) {
+    if !enemy_prefabs.has_spawnable_category(category_weights) {
+        errors.push(PrefabError::new(
+            "assets/prefabs/enemies",
+            String::from("no enemy category of enemy_category_weights has a prefab with a spawn weight")
+        ));
+    }
+}
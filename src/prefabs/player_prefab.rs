@@ -0,0 +1,264 @@
+use amethyst::{
+    core::{
+        Parent,
+        Transform,
+        nalgebra::Vector2
+    },
+    ecs::{
+        Entities,
+        Entity,
+        ReadExpect,
+        WriteStorage
+    },
+    renderer::SpriteRender
+};
+use crate::{
+    components::{
+        Cannon,
+        CollisionLayer,
+        Killable,
+        Moveable,
+        Rect,
+        Regeneration,
+        RigidBody,
+        Shield,
+        SpaceShip,
+        hitbox::{
+            Hitbox,
+            HitboxShape
+        },
+        tags::{
+            BoundInArenaTag,
+            PlayerShipTag
+        }
+    },
+    prefabs::{
+        CannonPrefabData,
+        FieldErrors,
+        SimplePrefab,
+        validate_hitbox,
+        validate_sprite
+    },
+    resources::{
+        SpriteNames,
+        SpriteRef,
+        SpriteSheets
+    }
+};
+use serde::{Serialize, Deserialize};
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum SpecialAbility {
+    Regeneration { interval: f32 },
+    Shield { charges: i32 }
+}
+
+impl SpecialAbility {
+
+    pub fn get_description(&self) -> String {
+        return match self {
+            SpecialAbility::Regeneration { interval } => format!("Restores 1 health every {}s", interval),
+            SpecialAbility::Shield { charges } => format!("Shield absorbs {} hits", charges)
+        };
+    }
+
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PlayerShipPrefabData {
+    pub name: String,
+    pub sprite: SpriteRef,
+    pub width: f32,
+    pub height: f32,
+    pub scale: f32,
+    pub movement_speed: f32,
+    pub max_health: i32,
+    pub health: i32,
+    pub mass: Option<f32>,
+    pub attack_cooldown: f64,
+    pub cannon_prefabs: Vec<CannonPrefabData>,
+    pub hitbox: Option<Vec<HitboxShape>>,
+    #[serde(default)]
+    pub abilities: Vec<SpecialAbility>
+}
+
+impl PlayerShipPrefabData {
+
+    // Damage output used to compare ships, missiles per second
+    pub fn get_fire_rate(&self) -> f32 {
+        return self.cannon_prefabs.len() as f32 / self.attack_cooldown as f32;
+    }
+
+    pub fn validate(&self, sprite_names: &SpriteNames) -> FieldErrors {
+        let mut errors = FieldErrors::new();
+
+        errors.check(!self.name.is_empty(), "name", "must not be empty");
+        validate_sprite(&self.sprite, "sprite", sprite_names, &mut errors);
+        errors.check(self.width > 0.0, "width", "must be positive");
+        errors.check(self.height > 0.0, "height", "must be positive");
+        errors.check(self.scale > 0.0, "scale", "must be positive");
+        errors.check(self.movement_speed > 0.0, "movement_speed", "must be positive");
+        errors.check(self.max_health > 0, "max_health", "must be positive");
+        errors.check(self.health > 0, "health", "must be positive");
+        errors.check(self.health <= self.max_health, "health", "must not be greater than max_health");
+        errors.check(self.attack_cooldown > 0.0, "attack_cooldown", "must be positive");
+        if let Some(mass) = self.mass {
+            errors.check(mass > 0.0, "mass", "must be positive");
+        }
+        for (i, cannon_prefab) in self.cannon_prefabs.iter().enumerate() {
+            cannon_prefab.validate(&format!("cannon_prefabs[{}]", i), sprite_names, &mut errors);
+        }
+        if let Some(hitbox) = &self.hitbox {
+            validate_hitbox(hitbox, &mut errors);
+        }
+        for (i, ability) in self.abilities.iter().enumerate() {
+            match ability {
+                SpecialAbility::Regeneration { interval } => errors.check(*interval > 0.0, &format!("abilities[{}].interval", i), "must be positive"),
+                SpecialAbility::Shield { charges } => errors.check(*charges > 0, &format!("abilities[{}].charges", i), "must be positive")
+            }
+        }
+
+        return errors;
+    }
+
+}
+
+impl<'a> SimplePrefab<'a> for PlayerShipPrefabData {
+    type SystemData = (
+        WriteStorage<'a, Transform>,
+        WriteStorage<'a, Rect>,
+        WriteStorage<'a, Moveable>,
+        WriteStorage<'a, Killable>,
+        WriteStorage<'a, SpaceShip>,
+        WriteStorage<'a, Cannon>,
+        WriteStorage<'a, PlayerShipTag>,
+        WriteStorage<'a, BoundInArenaTag>,
+        WriteStorage<'a, SpriteRender>,
+        WriteStorage<'a, Parent>,
+        WriteStorage<'a, CollisionLayer>,
+        WriteStorage<'a, Hitbox>,
+        WriteStorage<'a, RigidBody>,
+        WriteStorage<'a, Regeneration>,
+        WriteStorage<'a, Shield>,
+        ReadExpect<'a, SpriteSheets>
+    );
+
+    fn init(&mut self) {
+        // Apply scale to all dimensions
+        self.width = self.width * self.scale;
+        self.height = self.height * self.scale;
+
+        for cannon in &mut self.cannon_prefabs {
+            cannon.init(self.scale);
+        }
+
+        if let Some(hitbox) = &mut self.hitbox {
+            *hitbox = hitbox
+                .iter()
+                .map(|shape| shape.scaled(self.scale))
+                .collect();
+        }
+    }
+
+    fn create_entity(
+        &self,
+        entities: &'a Entities,
+        x: f32,
+        y: f32,
+        (
+            ref mut transforms,
+            ref mut rects,
+            ref mut moveables,
+            ref mut killables,
+            ref mut space_ships,
+            ref mut cannons,
+            ref mut player_ship_tags,
+            ref mut bound_in_arena_tags,
+            ref mut sprite_renders,
+            ref mut parents,
+            ref mut collision_layers,
+            ref mut hitboxes,
+            ref mut rigid_bodies,
+            ref mut regenerations,
+            ref mut shields,
+            sprite_sheets
+        ): &mut Self::SystemData
+    ) -> Entity {
+        let player_entity = entities.create();
+
+        let mut transform = Transform::default();
+        transform.set_xyz(x, y, 0.0);
+        transform.set_scale(self.scale, self.scale, 1.0);
+
+        transforms
+            .insert(player_entity, transform)
+            .expect("Could not create Transform!");
+        rects
+            .insert(player_entity, Rect {
+                width: self.width,
+                height: self.height
+            })
+            .expect("Could not create Rect!");
+        moveables
+            .insert(player_entity, Moveable {
+                move_speed: self.movement_speed,
+                direction: Vector2::new(0.0, 0.0)
+            })
+            .expect("Could not create Moveable!");
+        killables
+            .insert(player_entity, Killable::new_player(self.max_health, self.health))
+            .expect("Could not create Killable!");
+        player_ship_tags
+            .insert(player_entity, PlayerShipTag)
+            .expect("Could not create PlayerShipTag!");
+        bound_in_arena_tags
+            .insert(player_entity, BoundInArenaTag)
+            .expect("Could not create BoundInArenaTag!");
+        collision_layers
+            .insert(player_entity, CollisionLayer::Player)
+            .expect("Could not create CollisionLayer!");
+        rigid_bodies
+            .insert(player_entity, RigidBody::new(self.mass.unwrap_or(1.0)))
+            .expect("Could not create RigidBody!");
+        if let Some(hitbox) = &self.hitbox {
+            hitboxes
+                .insert(player_entity, Hitbox::new(hitbox.clone()))
+                .expect("Could not create Hitbox!");
+        }
+        let sprite_render = sprite_sheets
+            .get_sprite_render(&self.sprite)
+            .expect("Sprite should have been validated when loading the prefab!");
+        sprite_renders
+            .insert(player_entity, sprite_render)
+            .expect("Could not create SpriteRender!");
+        space_ships
+            .insert(player_entity, SpaceShip {
+                is_attacking: false
+            })
+            .expect("Could not create SpaceShip!");
+
+        for ability in &self.abilities {
+            match ability {
+                SpecialAbility::Regeneration { interval } => {
+                    regenerations
+                        .insert(player_entity, Regeneration::new(*interval))
+                        .expect("Could not create Regeneration!");
+                },
+                SpecialAbility::Shield { charges } => {
+                    shields
+                        .insert(player_entity, Shield::new(*charges))
+                        .expect("Could not create Shield!");
+                }
+            }
+        }
+
+        // Cannons are children of the ship and get deleted with it
+        for cannon_prefab in &self.cannon_prefabs {
+            cannon_prefab.create_entity(entities, player_entity, self.attack_cooldown, sprite_sheets, cannons, parents);
+        }
+
+        return player_entity;
+    }
+
+}
@@ -0,0 +1,202 @@
+use std::fmt;
+use amethyst::config::Config;
+use serde::{Serialize, Deserialize};
+use crate::{
+    components::hitbox::HitboxShape,
+    resources::{
+        SpriteNameMap,
+        SpriteNames,
+        SpriteRef
+    }
+};
+
+pub struct PrefabError {
+    file_name: String,
+    field: Option<String>,
+    line: Option<usize>,
+    message: String
+}
+
+impl PrefabError {
+
+    pub fn new(file_name: &str, message: String) -> Self {
+        return Self {
+            file_name: String::from(file_name),
+            field: None,
+            line: None,
+            message
+        };
+    }
+
+    pub fn with_line(file_name: &str, line: usize, message: String) -> Self {
+        return Self {
+            file_name: String::from(file_name),
+            field: None,
+            line: Some(line),
+            message
+        };
+    }
+
+    pub fn with_field(file_name: &str, source: &str, field: String, message: String) -> Self {
+        return Self {
+            file_name: String::from(file_name),
+            line: find_field_line(source, &field),
+            field: Some(field),
+            message
+        };
+    }
+
+}
+
+impl fmt::Display for PrefabError {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.file_name)?;
+        if let Some(line) = self.line {
+            write!(f, ":{}", line)?;
+        }
+        if let Some(field) = &self.field {
+            write!(f, " {}", field)?;
+        }
+        return write!(f, ": {}", self.message);
+    }
+
+}
+
+// Collects field errors of a single prefab, fields are paths like "cannon_prefabs[1].missile_speed"
+pub struct FieldErrors {
+    errors: Vec<(String, String)>
+}
+
+impl FieldErrors {
+
+    pub fn new() -> Self {
+        return Self {
+            errors: Vec::new()
+        };
+    }
+
+    pub fn check(&mut self, is_valid: bool, field: &str, message: &str) {
+        if !is_valid {
+            self.errors.push((String::from(field), String::from(message)));
+        }
+    }
+
+    pub fn into_prefab_errors(self, file_name: &str, source: &str) -> Vec<PrefabError> {
+        return self.errors
+            .into_iter()
+            .map(|(field, message)| PrefabError::with_field(file_name, source, field, message))
+            .collect();
+    }
+
+}
+
+pub fn validate_hitbox(shapes: &[HitboxShape], errors: &mut FieldErrors) {
+    for (i, shape) in shapes.iter().enumerate() {
+        let field = format!("hitbox[{}]", i);
+        match shape {
+            HitboxShape::Circle { radius, .. } => errors.check(*radius > 0.0, &format!("{}.radius", field), "must be positive"),
+            HitboxShape::Rect { width, height, .. } => {
+                errors.check(*width > 0.0, &format!("{}.width", field), "must be positive");
+                errors.check(*height > 0.0, &format!("{}.height", field), "must be positive");
+            },
+            HitboxShape::Polygon { points } => {
+                if points.len() < 3 {
+                    errors.check(false, &format!("{}.points", field), "needs at least 3 points");
+                } else {
+                    errors.check(is_convex(points), &format!("{}.points", field), "must form a convex polygon");
+                }
+            }
+        }
+    }
+}
+
+// Collisions use the separating axis test, which only holds for convex polygons. Every turn
+// along the outline has to go the same way, straight segments are allowed.
+fn is_convex(points: &[[f32; 2]]) -> bool {
+    let crosses: Vec<f32> = (0..points.len())
+        .map(|i| {
+            let (first, second, third) = (points[i], points[(i + 1) % points.len()], points[(i + 2) % points.len()]);
+            (second[0] - first[0]) * (third[1] - second[1]) - (second[1] - first[1]) * (third[0] - second[0])
+        })
+        .filter(|cross| *cross != 0.0)
+        .collect();
+    return !crosses.is_empty() && (crosses.iter().all(|cross| *cross > 0.0) || crosses.iter().all(|cross| *cross < 0.0));
+}
+
+// Looks for the line where field is declared, "cannon_prefabs[1].missile_speed" is the
+// second "missile_speed" key in the file
+fn find_field_line(source: &str, field: &str) -> Option<usize> {
+    let mut segments: Vec<&str> = field.split('.').collect();
+    let last_segment = segments.pop()?;
+    let key = match last_segment.find('[') {
+        Some(bracket_index) => &last_segment[..bracket_index],
+        None => last_segment
+    };
+    let occurrence = segments
+        .last()
+        .and_then(|segment| {
+            let start = segment.find('[')?;
+            let end = segment.find(']')?;
+            segment[start + 1..end].parse::<usize>().ok()
+        })
+        .unwrap_or(0);
+
+    let quoted_key = format!("\"{}\"", key);
+    return source
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.contains(&quoted_key))
+        .nth(occurrence)
+        .map(|(line_index, _)| line_index + 1);
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct SpriteDefinition {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct SpriteSheetDefinition {
+    spritesheet_width: u32,
+    spritesheet_height: u32,
+    sprites: Vec<SpriteDefinition>
+}
+
+// Checks a sprite reference used by a prefab field
+pub fn validate_sprite(sprite: &SpriteRef, field: &str, sprite_names: &SpriteNames, errors: &mut FieldErrors) {
+    if sprite_names.contains_sheet(&sprite.sheet) {
+        errors.check(sprite_names.contains(sprite), field, &format!("unknown sprite \"{}\"", sprite));
+    } else {
+        errors.check(false, field, &format!("unknown sprite sheet \"{}\"", sprite.sheet));
+    }
+}
+
+pub fn load_sprite_name_map(names_path: &str, definition_path: &str) -> Result<SpriteNameMap, String> {
+    let sprite_count = load_sprite_count(definition_path)?;
+    let name_map = SpriteNameMap::load_no_fallback(names_path)
+        .map_err(|err| format!("Could not read {}: {}", names_path, err))?;
+
+    let out_of_range = name_map.find_out_of_range(sprite_count);
+    if let Some((name, index)) = out_of_range.first() {
+        return Err(format!(
+            "{}: sprite \"{}\" has index {}, but {} only has {} sprites",
+            names_path,
+            name,
+            index,
+            definition_path,
+            sprite_count
+        ));
+    }
+
+    return Ok(name_map);
+}
+
+fn load_sprite_count(path: &str) -> Result<usize, String> {
+    return SpriteSheetDefinition::load_no_fallback(path)
+        .map(|sprite_sheet| sprite_sheet.sprites.len())
+        .map_err(|err| format!("Could not read {}: {}", path, err));
+}
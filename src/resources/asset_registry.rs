@@ -0,0 +1,146 @@
+use std::{
+    collections::HashMap,
+    path::Path
+};
+use amethyst::{
+    assets::{
+        AssetStorage,
+        Handle,
+        Loader,
+        ProgressCounter
+    },
+    prelude::*,
+    renderer::{
+        BmpFormat,
+        JpgFormat,
+        PngFormat,
+        SpriteSheet,
+        SpriteSheetFormat,
+        SpriteSheetHandle,
+        Texture,
+        TextureMetadata
+    },
+    ui::{
+        FontAsset,
+        TtfFormat
+    }
+};
+use serde::{Serialize, Deserialize};
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct SpriteSheetManifest {
+    pub image: String,
+    pub definition: String,
+    // Prefabs can only refer to sprites of sheets with a name map
+    #[serde(default)]
+    pub names: Option<String>
+}
+
+// Named assets to load, read from assets/config/asset_manifest.ron. Swapping the manifest swaps
+// every asset used by the game without touching the code.
+#[derive(Default, Serialize, Deserialize)]
+pub struct AssetManifest {
+    #[serde(default)]
+    pub textures: HashMap<String, String>,
+    #[serde(default)]
+    pub fonts: HashMap<String, String>,
+    #[serde(default)]
+    pub sprite_sheets: HashMap<String, SpriteSheetManifest>
+}
+
+#[derive(Default)]
+pub struct AssetRegistry {
+    textures: HashMap<String, Handle<Texture>>,
+    fonts: HashMap<String, Handle<FontAsset>>,
+    sprite_sheets: HashMap<String, SpriteSheetHandle>
+}
+
+impl AssetRegistry {
+
+    // Queues every asset of the manifest, returned errors are entries which could not be queued
+    pub fn load(manifest: &AssetManifest, world: &World, progress_counter: &mut ProgressCounter) -> (Self, Vec<String>) {
+        let loader = world.read_resource::<Loader>();
+        let texture_storage = world.read_resource::<AssetStorage<Texture>>();
+        let font_storage = world.read_resource::<AssetStorage<FontAsset>>();
+        let sprite_sheet_storage = world.read_resource::<AssetStorage<SpriteSheet>>();
+
+        let mut registry = AssetRegistry::default();
+        let mut errors: Vec<String> = Vec::new();
+
+        for (name, path) in &manifest.textures {
+            match load_texture(path, &loader, &texture_storage, progress_counter) {
+                Ok(texture) => {
+                    registry.textures.insert(name.clone(), texture);
+                },
+                Err(err) => errors.push(err)
+            }
+        }
+
+        for (name, path) in &manifest.fonts {
+            let font = loader.load(path.as_str(), TtfFormat, Default::default(), &mut *progress_counter, &font_storage);
+            registry.fonts.insert(name.clone(), font);
+        }
+
+        for (name, sprite_sheet) in &manifest.sprite_sheets {
+            match load_texture(&sprite_sheet.image, &loader, &texture_storage, progress_counter) {
+                Ok(texture) => {
+                    let sprite_sheet_handle = loader.load(
+                        sprite_sheet.definition.as_str(),
+                        SpriteSheetFormat,
+                        texture,
+                        &mut *progress_counter,
+                        &sprite_sheet_storage
+                    );
+                    registry.sprite_sheets.insert(name.clone(), sprite_sheet_handle);
+                },
+                Err(err) => errors.push(err)
+            }
+        }
+
+        return (registry, errors);
+    }
+
+    pub fn contains_font(&self, name: &str) -> bool {
+        return self.fonts.contains_key(name);
+    }
+
+    pub fn get_sprite_sheets(&self) -> HashMap<String, SpriteSheetHandle> {
+        return self.sprite_sheets.clone();
+    }
+
+    pub fn get_texture(&self, name: &str) -> Handle<Texture> {
+        return self.textures
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| panic!("Texture \"{}\" is missing from the asset manifest!", name));
+    }
+
+    pub fn get_font(&self, name: &str) -> Handle<FontAsset> {
+        return self.fonts
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| panic!("Font \"{}\" is missing from the asset manifest!", name));
+    }
+
+}
+
+fn get_extension(path: &str) -> Option<String> {
+    return Path::new(path)
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase());
+}
+
+fn load_texture(
+    path: &str,
+    loader: &Loader,
+    texture_storage: &AssetStorage<Texture>,
+    progress_counter: &mut ProgressCounter
+) -> Result<Handle<Texture>, String> {
+    let metadata = TextureMetadata::srgb_scale();
+    return match get_extension(path).as_ref().map(String::as_str) {
+        Some("png") => Ok(loader.load(path, PngFormat, metadata, progress_counter, texture_storage)),
+        Some("jpg") | Some("jpeg") => Ok(loader.load(path, JpgFormat, metadata, progress_counter, texture_storage)),
+        Some("bmp") => Ok(loader.load(path, BmpFormat, metadata, progress_counter, texture_storage)),
+        _ => Err(format!("{}: unsupported texture format", path))
+    };
+}
@@ -0,0 +1,64 @@
+use std::{
+    fs,
+    path::Path
+};
+use amethyst::utils::application_root_dir;
+use log::warn;
+use serde::{Serialize, Deserialize};
+use crate::constants;
+
+// Campaign progress, written to the save file whenever a level is completed
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct CampaignSave {
+    unlocked_levels: usize
+}
+
+impl Default for CampaignSave {
+
+    fn default() -> Self {
+        return Self {
+            unlocked_levels: 1
+        };
+    }
+
+}
+
+impl CampaignSave {
+
+    fn get_path() -> String {
+        return format!("{}/{}", application_root_dir(), constants::CAMPAIGN_SAVE_PATH);
+    }
+
+    // A missing save file is a new campaign, an unreadable one is logged and started over
+    pub fn load() -> Self {
+        let path = CampaignSave::get_path();
+        let source = match fs::read_to_string(&path) {
+            Ok(source) => source,
+            Err(_) => return CampaignSave::default()
+        };
+
+        return serde_json::from_str(&source).unwrap_or_else(|err| {
+            warn!("Could not read campaign save {}: {}", path, err);
+            CampaignSave::default()
+        });
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = CampaignSave::get_path();
+        if let Some(directory) = Path::new(&path).parent() {
+            fs::create_dir_all(directory).map_err(|err| format!("Could not create {}: {}", directory.display(), err))?;
+        }
+        let source = serde_json::to_string_pretty(self).map_err(|err| err.to_string())?;
+        return fs::write(&path, source).map_err(|err| format!("Could not write {}: {}", path, err));
+    }
+
+    pub fn is_unlocked(&self, level_index: usize) -> bool {
+        return level_index < self.unlocked_levels;
+    }
+
+    pub fn unlock(&mut self, level_index: usize) {
+        self.unlocked_levels = self.unlocked_levels.max(level_index + 1);
+    }
+
+}
@@ -0,0 +1,267 @@
+use std::collections::{
+    HashMap,
+    HashSet
+};
+use amethyst::ecs::Entity;
+use crate::utils::Aabb;
+
+const DEFAULT_CELL_SIZE: f32 = 64.0;
+
+// Uniform grid broadphase, rebuilt every tick by CollisionGridSystem
+pub struct CollisionGrid {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<Entity>>,
+    bounds: HashMap<Entity, Aabb>
+}
+
+impl Default for CollisionGrid {
+
+    fn default() -> Self {
+        return CollisionGrid::new(DEFAULT_CELL_SIZE);
+    }
+
+}
+
+impl CollisionGrid {
+
+    pub fn new(cell_size: f32) -> Self {
+        return Self {
+            cell_size,
+            cells: HashMap::new(),
+            bounds: HashMap::new()
+        };
+    }
+
+    pub fn clear(&mut self) {
+        // Keep allocated cell vectors, entities tend to stay in the same cells between ticks
+        for cell in self.cells.values_mut() {
+            cell.clear();
+        }
+        self.bounds.clear();
+    }
+
+    pub fn insert(&mut self, entity: Entity, aabb: Aabb) {
+        let (min_x, min_y, max_x, max_y) = self.cell_range(&aabb);
+        for cell_x in min_x..=max_x {
+            for cell_y in min_y..=max_y {
+                self.cells
+                    .entry((cell_x, cell_y))
+                    .or_insert_with(Vec::new)
+                    .push(entity);
+            }
+        }
+
+        self.bounds.insert(entity, aabb);
+    }
+
+    // Returns every entity whose bounds overlap given area
+    pub fn query(&self, aabb: &Aabb) -> Vec<Entity> {
+        let mut visited: HashSet<Entity> = HashSet::new();
+        let mut result: Vec<Entity> = Vec::new();
+
+        let (min_x, min_y, max_x, max_y) = self.cell_range(aabb);
+        for cell_x in min_x..=max_x {
+            for cell_y in min_y..=max_y {
+                if let Some(cell) = self.cells.get(&(cell_x, cell_y)) {
+                    for entity in cell {
+                        if visited.insert(*entity) && self.bounds[entity].intersects(aabb) {
+                            result.push(*entity);
+                        }
+                    }
+                }
+            }
+        }
+
+        return result;
+    }
+
+    // Returns every other entity colliding with given entity
+    pub fn query_entity(&self, entity: Entity) -> Vec<Entity> {
+        return match self.bounds.get(&entity) {
+            Some(aabb) => self.query(aabb)
+                .into_iter()
+                .filter(|other| *other != entity)
+                .collect(),
+            None => Vec::new()
+        };
+    }
+
+    fn cell_range(&self, aabb: &Aabb) -> (i32, i32, i32, i32) {
+        return (
+            (aabb.left / self.cell_size).floor() as i32,
+            (aabb.bottom / self.cell_size).floor() as i32,
+            (aabb.right / self.cell_size).floor() as i32,
+            (aabb.top / self.cell_size).floor() as i32
+        );
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+    use amethyst::ecs::{
+        Builder,
+        World
+    };
+    use rand::{
+        rngs::StdRng,
+        Rng,
+        SeedableRng
+    };
+    use super::*;
+
+    fn aabb(left: f32, bottom: f32, width: f32, height: f32) -> Aabb {
+        return Aabb {
+            left,
+            right: left + width,
+            bottom,
+            top: bottom + height
+        };
+    }
+
+    fn create_entities(world: &mut World, count: usize) -> Vec<Entity> {
+        return (0..count).map(|_| world.create_entity().build()).collect();
+    }
+
+    #[test]
+    fn insert_buckets_entity_into_every_overlapped_cell() {
+        let mut world = World::new();
+        let entities = create_entities(&mut world, 1);
+        let mut grid = CollisionGrid::new(10.0);
+
+        grid.insert(entities[0], aabb(5.0, 5.0, 10.0, 10.0));
+
+        for cell in &[(0, 0), (0, 1), (1, 0), (1, 1)] {
+            assert_eq!(grid.cells[cell], vec![entities[0]]);
+        }
+        assert_eq!(grid.cells.len(), 4);
+    }
+
+    #[test]
+    fn negative_coordinates_use_cells_below_zero() {
+        let mut world = World::new();
+        let entities = create_entities(&mut world, 1);
+        let mut grid = CollisionGrid::new(10.0);
+
+        grid.insert(entities[0], aabb(-5.0, -15.0, 2.0, 2.0));
+
+        assert_eq!(grid.cells[&(-1, -2)], vec![entities[0]]);
+        assert_eq!(grid.cells.len(), 1);
+    }
+
+    #[test]
+    fn query_returns_overlapping_entities_once() {
+        let mut world = World::new();
+        let entities = create_entities(&mut world, 3);
+        let mut grid = CollisionGrid::new(10.0);
+
+        // Spans four cells, must still be returned once
+        grid.insert(entities[0], aabb(5.0, 5.0, 10.0, 10.0));
+        // Same cell as the query but not overlapping it
+        grid.insert(entities[1], aabb(18.0, 18.0, 1.0, 1.0));
+        grid.insert(entities[2], aabb(50.0, 50.0, 5.0, 5.0));
+
+        assert_eq!(grid.query(&aabb(12.0, 12.0, 4.0, 4.0)), vec![entities[0]]);
+        assert!(grid.query(&aabb(100.0, 100.0, 1.0, 1.0)).is_empty());
+    }
+
+    #[test]
+    fn query_entity_returns_other_colliding_entities() {
+        let mut world = World::new();
+        let entities = create_entities(&mut world, 3);
+        let mut grid = CollisionGrid::new(10.0);
+
+        grid.insert(entities[0], aabb(0.0, 0.0, 10.0, 10.0));
+        grid.insert(entities[1], aabb(8.0, 8.0, 10.0, 10.0));
+        grid.insert(entities[2], aabb(30.0, 30.0, 5.0, 5.0));
+
+        assert_eq!(grid.query_entity(entities[0]), vec![entities[1]]);
+        assert_eq!(grid.query_entity(entities[1]), vec![entities[0]]);
+        assert!(grid.query_entity(entities[2]).is_empty());
+    }
+
+    #[test]
+    fn clear_removes_every_entity() {
+        let mut world = World::new();
+        let entities = create_entities(&mut world, 2);
+        let mut grid = CollisionGrid::new(10.0);
+
+        grid.insert(entities[0], aabb(0.0, 0.0, 10.0, 10.0));
+        grid.insert(entities[1], aabb(5.0, 5.0, 10.0, 10.0));
+        grid.clear();
+
+        assert!(grid.query(&aabb(0.0, 0.0, 20.0, 20.0)).is_empty());
+        assert!(grid.query_entity(entities[0]).is_empty());
+    }
+
+    // Compares the grid with the pairwise loop the collision systems used before,
+    // run with `cargo test --release -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn benchmark_against_pairwise_loop() {
+        const PROJECTILE_COUNT: usize = 400;
+        const TARGET_COUNT: usize = 100;
+        const TICKS: u32 = 100;
+
+        let mut world = World::new();
+        let projectiles = create_entities(&mut world, PROJECTILE_COUNT);
+        let targets = create_entities(&mut world, TARGET_COUNT);
+        let mut rng = StdRng::seed_from_u64(27);
+        let mut random_aabbs = |count: usize, size: f32| -> Vec<Aabb> {
+            return (0..count)
+                .map(|_| aabb(rng.gen_range(0.0, 750.0), rng.gen_range(0.0, 900.0), size, size))
+                .collect();
+        };
+        let projectile_aabbs = random_aabbs(PROJECTILE_COUNT, 10.0);
+        let target_aabbs = random_aabbs(TARGET_COUNT, 60.0);
+
+        let pairwise_start = Instant::now();
+        let mut pairwise_pairs = Vec::new();
+        for _ in 0..TICKS {
+            pairwise_pairs.clear();
+            for (projectile, projectile_aabb) in projectiles.iter().zip(&projectile_aabbs) {
+                for (target, target_aabb) in targets.iter().zip(&target_aabbs) {
+                    if projectile_aabb.intersects(target_aabb) {
+                        pairwise_pairs.push((*projectile, *target));
+                    }
+                }
+            }
+        }
+        let pairwise_time = pairwise_start.elapsed();
+
+        // Rebuilt every tick, like CollisionGridSystem does
+        let target_set: HashSet<Entity> = targets.iter().cloned().collect();
+        let grid_start = Instant::now();
+        let mut grid = CollisionGrid::default();
+        let mut grid_pairs = Vec::new();
+        for _ in 0..TICKS {
+            grid.clear();
+            grid_pairs.clear();
+            for (entity, entity_aabb) in projectiles.iter().chain(&targets).zip(projectile_aabbs.iter().chain(&target_aabbs)) {
+                grid.insert(*entity, entity_aabb.clone());
+            }
+            for projectile in &projectiles {
+                for other in grid.query_entity(*projectile) {
+                    if target_set.contains(&other) {
+                        grid_pairs.push((*projectile, other));
+                    }
+                }
+            }
+        }
+        let grid_time = grid_start.elapsed();
+
+        pairwise_pairs.sort();
+        grid_pairs.sort();
+        assert_eq!(grid_pairs, pairwise_pairs);
+        println!(
+            "{} projectiles, {} targets, {} ticks: pairwise {:?}, grid {:?}",
+            PROJECTILE_COUNT,
+            TARGET_COUNT,
+            TICKS,
+            pairwise_time,
+            grid_time
+        );
+    }
+
+}
@@ -0,0 +1,36 @@
+use serde::{Serialize, Deserialize};
+use crate::components::CollisionLayer;
+
+// Which collision layers interact with each other, loaded from assets/config/collision_matrix.ron
+#[derive(Serialize, Deserialize)]
+pub struct CollisionMatrix {
+    interactions: Vec<(CollisionLayer, CollisionLayer)>
+}
+
+impl Default for CollisionMatrix {
+
+    fn default() -> Self {
+        return Self {
+            interactions: vec![
+                (CollisionLayer::Player, CollisionLayer::Enemy),
+                (CollisionLayer::Player, CollisionLayer::EnemyProjectile),
+                (CollisionLayer::Player, CollisionLayer::Pickup),
+                (CollisionLayer::Enemy, CollisionLayer::PlayerProjectile)
+            ]
+        };
+    }
+
+}
+
+impl CollisionMatrix {
+
+    pub fn is_interacting(&self, first_layer: CollisionLayer, second_layer: CollisionLayer) -> bool {
+        return self.interactions
+            .iter()
+            .any(|(first, second)| {
+                (*first == first_layer && *second == second_layer) ||
+                (*first == second_layer && *second == first_layer)
+            });
+    }
+
+}
@@ -0,0 +1,142 @@
+const MAX_OUTPUT_LINES: usize = 100;
+const HELP: [&str; 8] = [
+    "spawn <prefab> [x]  spawns an enemy at the top of the arena",
+    "give <health|shield>  gives a pickup to every player",
+    "god  toggles player invulnerability",
+    "set_score <score> [player]  sets the score of a player, first by default",
+    "timescale <n>  changes game speed",
+    "kill_all  removes every enemy",
+    "reload_prefabs  reloads enemy and player prefab files",
+    "help  lists commands"
+];
+
+pub enum ConsoleCommand {
+    Spawn { prefab: String, x: Option<f32> },
+    Give { pickup: String },
+    God,
+    SetScore { score: i32, player_index: usize },
+    TimeScale(f32),
+    KillAll,
+    ReloadPrefabs
+}
+
+impl ConsoleCommand {
+
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        if words.is_empty() {
+            return Err(String::from("empty command"));
+        }
+
+        return match words.as_slice() {
+            ["spawn", prefab] => Ok(ConsoleCommand::Spawn { prefab: prefab.to_string(), x: None }),
+            ["spawn", prefab, x] => Ok(ConsoleCommand::Spawn { prefab: prefab.to_string(), x: Some(parse_arg(x, "x")?) }),
+            ["give", pickup] => Ok(ConsoleCommand::Give { pickup: pickup.to_string() }),
+            ["god"] => Ok(ConsoleCommand::God),
+            ["set_score", score] => Ok(ConsoleCommand::SetScore { score: parse_arg(score, "score")?, player_index: 0 }),
+            ["set_score", score, player] => {
+                let player: usize = parse_arg(player, "player")?;
+                if player == 0 {
+                    return Err(String::from("players are numbered from 1"));
+                }
+                Ok(ConsoleCommand::SetScore { score: parse_arg(score, "score")?, player_index: player - 1 })
+            },
+            ["timescale", time_scale] => {
+                let time_scale: f32 = parse_arg(time_scale, "timescale")?;
+                if time_scale < 0.0 {
+                    return Err(String::from("timescale must not be negative"));
+                }
+                Ok(ConsoleCommand::TimeScale(time_scale))
+            },
+            ["kill_all"] => Ok(ConsoleCommand::KillAll),
+            ["reload_prefabs"] => Ok(ConsoleCommand::ReloadPrefabs),
+            _ => Err(format!("unknown command or wrong arguments: {}, type help for commands", words[0]))
+        };
+    }
+
+}
+
+fn parse_arg<T: std::str::FromStr>(value: &str, name: &str) -> Result<T, String> {
+    return value
+        .parse()
+        .map_err(|_| format!("invalid {}: {}", name, value));
+}
+
+// Developer console state, the console system edits the input and gameplay executes the
+// submitted commands
+#[derive(Default)]
+pub struct Console {
+    pub is_open: bool,
+    pub input: String,
+    history: Vec<String>,
+    history_index: Option<usize>,
+    output: Vec<String>,
+    pending_commands: Vec<ConsoleCommand>
+}
+
+impl Console {
+
+    pub fn submit(&mut self) {
+        let line = self.input.trim().to_string();
+        self.input.clear();
+        self.history_index = None;
+        if line.is_empty() {
+            return;
+        }
+
+        self.print(format!("> {}", line));
+        if self.history.last() != Some(&line) {
+            self.history.push(line.clone());
+        }
+
+        if line == "help" {
+            HELP.iter().for_each(|help| self.print(help.to_string()));
+            return;
+        }
+        match ConsoleCommand::parse(&line) {
+            Ok(command) => self.pending_commands.push(command),
+            Err(err) => self.print(err)
+        }
+    }
+
+    pub fn previous_history(&mut self) {
+        if self.history.is_empty() {
+            return;
+        }
+        let index = match self.history_index {
+            Some(index) => index.saturating_sub(1),
+            None => self.history.len() - 1
+        };
+        self.history_index = Some(index);
+        self.input = self.history[index].clone();
+    }
+
+    pub fn next_history(&mut self) {
+        match self.history_index {
+            Some(index) if index + 1 < self.history.len() => {
+                self.history_index = Some(index + 1);
+                self.input = self.history[index + 1].clone();
+            },
+            _ => {
+                self.history_index = None;
+                self.input.clear();
+            }
+        }
+    }
+
+    pub fn print(&mut self, line: String) {
+        self.output.push(line);
+        if self.output.len() > MAX_OUTPUT_LINES {
+            self.output.remove(0);
+        }
+    }
+
+    pub fn get_last_output(&self, count: usize) -> &[String] {
+        return &self.output[self.output.len().saturating_sub(count)..];
+    }
+
+    pub fn take_commands(&mut self) -> Vec<ConsoleCommand> {
+        return self.pending_commands.drain(..).collect();
+    }
+
+}
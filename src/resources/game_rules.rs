@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+
+// Gameplay values loaded from assets/config/game_rules.ron, missing entries keep their default
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameRules {
+    pub arena_width: f32,
+    pub arena_height: f32,
+    pub enemy_spawner_min_delay: f64,
+    pub enemy_spawner_max_delay: f64,
+    // Chance of the endless spawner picking each enemy category, unlisted categories are left to
+    // campaign levels and the console
+    pub enemy_category_weights: HashMap<String, f64>,
+    pub pickup_expire_time: f64,
+    pub health_pickup_drop_chance: f64,
+    pub player_movement_speed_multiplier: f32,
+    pub player_bonus_health: i32
+}
+
+impl Default for GameRules {
+
+    fn default() -> Self {
+        return Self {
+            arena_width: 750.0,
+            arena_height: 900.0,
+            enemy_spawner_min_delay: 0.5,
+            enemy_spawner_max_delay: 2.0,
+            enemy_category_weights: [("small", 0.6), ("medium", 0.3), ("large", 0.1)]
+                .iter()
+                .map(|(category, weight)| (String::from(*category), *weight))
+                .collect(),
+            pickup_expire_time: 5.0,
+            health_pickup_drop_chance: 1.0,
+            player_movement_speed_multiplier: 1.0,
+            player_bonus_health: 0
+        };
+    }
+
+}
+
+impl GameRules {
+
+    // Overrides a single rule from a "key=value" command line argument
+    pub fn apply_override(&mut self, rule: &str) -> Result<(), String> {
+        let mut parts = rule.splitn(2, '=');
+        let (key, value) = match (parts.next(), parts.next()) {
+            (Some(key), Some(value)) => (key.trim(), value.trim()),
+            _ => return Err(format!("\"{}\": expected key=value", rule))
+        };
+
+        let mut rules = serde_json::to_value(&*self).map_err(|err| err.to_string())?;
+        match rules.get_mut(key) {
+            Some(field) => {
+                *field = serde_json::from_str(value).unwrap_or_else(|_| Value::String(String::from(value)));
+            },
+            None => return Err(format!("\"{}\": unknown rule", key))
+        }
+
+        *self = serde_json::from_value(rules).map_err(|err| format!("\"{}\": {}", rule, err))?;
+        return self.validate();
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.arena_width <= 0.0 || self.arena_height <= 0.0 {
+            return Err(String::from("arena size must be positive"));
+        }
+        if self.enemy_spawner_min_delay <= 0.0 || self.enemy_spawner_min_delay >= self.enemy_spawner_max_delay {
+            return Err(String::from("enemy spawner delays must be positive and min must be less than max"));
+        }
+        if self.enemy_category_weights.values().any(|weight| *weight < 0.0) {
+            return Err(String::from("enemy_category_weights must not be negative"));
+        }
+        if !self.enemy_category_weights.values().any(|weight| *weight > 0.0) {
+            return Err(String::from("enemy_category_weights needs at least one positive weight"));
+        }
+        if self.pickup_expire_time <= 0.0 {
+            return Err(String::from("pickup_expire_time must be positive"));
+        }
+        if self.health_pickup_drop_chance < 0.0 || self.health_pickup_drop_chance > 1.0 {
+            return Err(String::from("health_pickup_drop_chance must be between 0 and 1"));
+        }
+        if self.player_movement_speed_multiplier <= 0.0 {
+            return Err(String::from("player_movement_speed_multiplier must be positive"));
+        }
+        return Ok(());
+    }
+
+}
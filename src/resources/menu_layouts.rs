@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+use amethyst::ui::Anchor;
+use serde::{Serialize, Deserialize};
+use crate::resources::AssetManifest;
+
+// Font refers to a font of the asset manifest
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TextLayout {
+    pub font: String,
+    pub font_size: f32,
+    pub color: [f32; 4]
+}
+
+// Title is anchored to the top middle of the screen
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TitleLayout {
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub text: TextLayout
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ButtonPlacement {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32
+}
+
+// Buttons are stacked downwards starting at y, unless the button at the same index has a placement.
+// Images refer to textures of the asset manifest.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ButtonsLayout {
+    pub anchor: Anchor,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub spacing: f32,
+    pub text: TextLayout,
+    pub image: String,
+    pub hover_image: String,
+    #[serde(default)]
+    pub placements: Vec<ButtonPlacement>
+}
+
+// Text lines below the title, anchored to the top middle of the screen
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LinesLayout {
+    pub y: f32,
+    pub width: f32,
+    pub line_height: f32,
+    pub text: TextLayout
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MenuLayout {
+    pub title: TitleLayout,
+    pub buttons: ButtonsLayout,
+    pub lines: LinesLayout
+}
+
+// Menu layouts by name, read from assets/ui/layouts/menus.ron
+#[derive(Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct MenuLayouts {
+    layouts: HashMap<String, MenuLayout>
+}
+
+impl MenuLayouts {
+
+    pub fn get(&self, name: &str) -> Option<&MenuLayout> {
+        return self.layouts.get(name);
+    }
+
+    // Every referenced font and texture has to be in the asset manifest, so restyling can't
+    // break a menu after loading
+    pub fn validate(&self, required_layouts: &[&str], manifest: &AssetManifest) -> Vec<String> {
+        let mut errors: Vec<String> = required_layouts
+            .iter()
+            .filter(|name| !self.layouts.contains_key(**name))
+            .map(|name| format!("layout \"{}\" is required by the game", name))
+            .collect();
+
+        let mut names: Vec<&String> = self.layouts.keys().collect();
+        names.sort();
+        for name in names {
+            let layout = &self.layouts[name];
+            let fonts = [&layout.title.text.font, &layout.buttons.text.font, &layout.lines.text.font];
+            for font in fonts.iter().filter(|font| !manifest.fonts.contains_key(**font)) {
+                errors.push(format!("{}: font \"{}\" is missing from the asset manifest", name, font));
+            }
+            let textures = [&layout.buttons.image, &layout.buttons.hover_image];
+            for texture in textures.iter().filter(|texture| !manifest.textures.contains_key(**texture)) {
+                errors.push(format!("{}: texture \"{}\" is missing from the asset manifest", name, texture));
+            }
+        }
+
+        return errors;
+    }
+
+}
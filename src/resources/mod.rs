@@ -1,93 +1,115 @@
 use amethyst::{
-    assets::{
-        Handle
-    },
+    assets::Handle,
     ecs::Entity,
-    renderer::{
-        Texture
+    renderer::Texture
+};
+
+mod asset_registry;
+mod campaign_save;
+mod collision_grid;
+mod collision_matrix;
+mod console;
+mod game_rules;
+mod menu_layouts;
+mod sprite_sheets;
+mod system_timings;
+
+pub use {
+    asset_registry::{
+        AssetManifest,
+        AssetRegistry,
+        SpriteSheetManifest
+    },
+    campaign_save::CampaignSave,
+    collision_grid::CollisionGrid,
+    collision_matrix::CollisionMatrix,
+    console::{
+        Console,
+        ConsoleCommand
+    },
+    game_rules::GameRules,
+    menu_layouts::{
+        MenuLayout,
+        MenuLayouts,
+        TextLayout
     },
-    ui::FontAsset
+    sprite_sheets::{
+        SpriteNameMap,
+        SpriteNames,
+        SpriteRef,
+        SpriteSheets
+    },
+    system_timings::SystemTimings
 };
 
 #[derive(Clone)]
 pub enum GameState {
     Running,
     Paused,
-    Finished
+    Finished,
+    LevelComplete
 }
 
-pub struct GameplayNextState {
-    pub next_state: Option<GameState>
+// Where the arena is drawn in the window, in pixels from the bottom left corner
+pub struct ArenaViewport {
+    pub x_offset: f32,
+    pub y_offset: f32,
+    pub scale: f32
 }
 
-pub struct UiAssets {
-    font: Handle<FontAsset>,
-    btn_img: Handle<Texture>,
-    btn_hover_img: Handle<Texture>,
-    life_img: Handle<Texture>,
-    health_bar_border_img: Handle<Texture>,
-    health_bar_green_img: Handle<Texture>,
-    health_bar_red_img: Handle<Texture>
-}
+impl Default for ArenaViewport {
 
-impl UiAssets {
-
-    pub fn new(
-        font: Handle<FontAsset>,
-        btn_img: Handle<Texture>,
-        btn_hover_img: Handle<Texture>,
-        life_img: Handle<Texture>,
-        health_bar_border_img: Handle<Texture>,
-        health_bar_green_img: Handle<Texture>,
-        health_bar_red_img: Handle<Texture>
-    ) -> Self {
-        return UiAssets {
-            font,
-            btn_img,
-            btn_hover_img,
-            life_img,
-            health_bar_border_img,
-            health_bar_green_img,
-            health_bar_red_img
+    fn default() -> Self {
+        return Self {
+            x_offset: 0.0,
+            y_offset: 0.0,
+            scale: 1.0
         };
     }
 
-    pub fn get_font(&self) -> Handle<FontAsset> {
-        return self.font.clone();
-    }
+}
 
-    pub fn get_btn_img(&self) -> Handle<Texture> {
-        return self.btn_img.clone();
-    }
+impl ArenaViewport {
 
-    pub fn get_btn_hover_img(&self) -> Handle<Texture> {
-        return self.btn_hover_img.clone();
+    pub fn to_screen(&self, x: f32, y: f32) -> (f32, f32) {
+        return (self.x_offset + x * self.scale, self.y_offset + y * self.scale);
     }
 
-    pub fn get_life_img(&self) -> Handle<Texture> {
-        return self.life_img.clone();
-    }
+}
 
-    pub fn get_health_bar_border_img(&self) -> Handle<Texture> {
-        return self.health_bar_border_img.clone();
-    }
+// Options changed from the options menu, systems apply them when they differ from the current
+// state
+pub struct GameOptions {
+    pub is_fullscreen: bool,
+    pub is_pause_on_focus_lost: bool
+}
 
-    pub fn get_health_bar_green_img(&self) -> Handle<Texture> {
-        return self.health_bar_green_img.clone();
-    }
+impl Default for GameOptions {
 
-    pub fn get_health_bar_red_img(&self) -> Handle<Texture> {
-        return self.health_bar_red_img.clone();
+    fn default() -> Self {
+        return Self {
+            is_fullscreen: false,
+            is_pause_on_focus_lost: true
+        };
     }
 
 }
 
-pub struct UiGameplayElements {
+// Pause screens are created on every pause, the overlay texture is loaded once
+pub struct PauseOverlayTexture {
+    pub texture: Handle<Texture>
+}
+
+pub struct GameplayNextState {
+    pub next_state: Option<GameState>
+}
+
+pub struct UiPlayerElements {
     pub score_value_text: Entity,
     pub life_value_text: Entity
 }
 
-impl UiGameplayElements {
+impl UiPlayerElements {
 
     pub fn new(score_value_text: Entity, life_value_text: Entity) -> Self {
         return Self {
@@ -98,6 +120,81 @@ impl UiGameplayElements {
 
 }
 
+pub struct UiGameplayElements {
+    pub players: Vec<UiPlayerElements>
+}
+
+impl UiGameplayElements {
+
+    pub fn new(players: Vec<UiPlayerElements>) -> Self {
+        return Self {
+            players
+        };
+    }
+
+}
+
 pub struct GameplaySessionData {
-    pub score: i32
+    scores: Vec<i32>,
+    elapsed_time: f32,
+    enemies_destroyed: i32,
+    // Game speed outside of pauses, changed from the console
+    time_scale: f32
+}
+
+impl GameplaySessionData {
+
+    pub fn new(player_count: usize) -> Self {
+        return Self {
+            scores: vec![0; player_count],
+            elapsed_time: 0.0,
+            enemies_destroyed: 0,
+            time_scale: 1.0
+        };
+    }
+
+    pub fn add_score(&mut self, player_index: usize, points: i32) {
+        self.scores[player_index] += points;
+    }
+
+    pub fn set_score(&mut self, player_index: usize, score: i32) {
+        self.scores[player_index] = score;
+    }
+
+    pub fn get_score(&self, player_index: usize) -> i32 {
+        return self.scores[player_index];
+    }
+
+    pub fn add_elapsed_time(&mut self, delta_seconds: f32) {
+        self.elapsed_time += delta_seconds;
+    }
+
+    pub fn get_elapsed_time(&self) -> f32 {
+        return self.elapsed_time;
+    }
+
+    pub fn add_enemy_destroyed(&mut self) {
+        self.enemies_destroyed += 1;
+    }
+
+    pub fn get_enemies_destroyed(&self) -> i32 {
+        return self.enemies_destroyed;
+    }
+
+    pub fn get_total_score(&self) -> i32 {
+        return self.scores.iter().sum();
+    }
+
+    pub fn get_player_count(&self) -> usize {
+        return self.scores.len();
+    }
+
+    pub fn set_time_scale(&mut self, time_scale: f32) {
+        self.time_scale = time_scale;
+    }
+
+    pub fn get_time_scale(&self) -> f32 {
+        return self.time_scale;
+    }
+
 }
\ No newline at end of file
@@ -0,0 +1,116 @@
+use std::{
+    collections::HashMap,
+    fmt
+};
+use amethyst::renderer::{
+    SpriteRender,
+    SpriteSheetHandle
+};
+use serde::{Serialize, Deserialize};
+
+// Reference to a named sprite of a named sprite sheet, as written in prefab files
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SpriteRef {
+    pub sheet: String,
+    pub name: String
+}
+
+impl SpriteRef {
+
+    pub fn new(sheet: &str, name: &str) -> Self {
+        return Self {
+            sheet: String::from(sheet),
+            name: String::from(name)
+        };
+    }
+
+}
+
+impl fmt::Display for SpriteRef {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "{}/{}", self.sheet, self.name);
+    }
+
+}
+
+// Sprite indices by name of a single sprite sheet, read from the name map next to its definition
+#[derive(Default, Serialize, Deserialize)]
+pub struct SpriteNameMap {
+    sprites: HashMap<String, usize>
+}
+
+impl SpriteNameMap {
+
+    // Names pointing past the end of the sprite sheet
+    pub fn find_out_of_range(&self, sprite_count: usize) -> Vec<(&str, usize)> {
+        let mut out_of_range: Vec<(&str, usize)> = self.sprites
+            .iter()
+            .filter(|(_, index)| **index >= sprite_count)
+            .map(|(name, index)| (name.as_str(), *index))
+            .collect();
+        out_of_range.sort();
+        return out_of_range;
+    }
+
+}
+
+// Sprite names of every sprite sheet, available before the sprite sheets themselves are loaded
+#[derive(Default)]
+pub struct SpriteNames {
+    sheets: HashMap<String, SpriteNameMap>
+}
+
+impl SpriteNames {
+
+    pub fn insert(&mut self, sheet: &str, name_map: SpriteNameMap) {
+        self.sheets.insert(String::from(sheet), name_map);
+    }
+
+    pub fn contains_sheet(&self, sheet: &str) -> bool {
+        return self.sheets.contains_key(sheet);
+    }
+
+    pub fn get(&self, sprite: &SpriteRef) -> Option<usize> {
+        return self.sheets
+            .get(&sprite.sheet)
+            .and_then(|name_map| name_map.sprites.get(&sprite.name))
+            .cloned();
+    }
+
+    pub fn contains(&self, sprite: &SpriteRef) -> bool {
+        return self.get(sprite).is_some();
+    }
+
+}
+
+// Resolves sprite references to renderable sprites of any loaded sprite sheet
+#[derive(Default)]
+pub struct SpriteSheets {
+    names: SpriteNames,
+    handles: HashMap<String, SpriteSheetHandle>
+}
+
+impl SpriteSheets {
+
+    pub fn new(names: SpriteNames, handles: HashMap<String, SpriteSheetHandle>) -> Self {
+        return Self {
+            names,
+            handles
+        };
+    }
+
+    pub fn get_names(&self) -> &SpriteNames {
+        return &self.names;
+    }
+
+    pub fn get_sprite_render(&self, sprite: &SpriteRef) -> Option<SpriteRender> {
+        let sprite_number = self.names.get(sprite)?;
+        let sprite_sheet = self.handles.get(&sprite.sheet)?.clone();
+        return Some(SpriteRender {
+            sprite_sheet,
+            sprite_number
+        });
+    }
+
+}
@@ -0,0 +1,34 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::Duration
+};
+
+// Last run duration of every timed gameplay system
+#[derive(Default)]
+pub struct SystemTimings {
+    timings: Mutex<HashMap<&'static str, Duration>>
+}
+
+impl SystemTimings {
+
+    pub fn record(&self, name: &'static str, duration: Duration) {
+        self.timings
+            .lock()
+            .expect("System timings lock was poisoned!")
+            .insert(name, duration);
+    }
+
+    // Slowest systems first
+    pub fn get_sorted(&self) -> Vec<(&'static str, Duration)> {
+        let mut timings: Vec<(&'static str, Duration)> = self.timings
+            .lock()
+            .expect("System timings lock was poisoned!")
+            .iter()
+            .map(|(name, duration)| (*name, *duration))
+            .collect();
+        timings.sort_by(|(_, first), (_, second)| second.cmp(first));
+        return timings;
+    }
+
+}
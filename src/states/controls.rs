@@ -0,0 +1,118 @@
+use amethyst::{
+    input::{
+        self,
+        Axis,
+        Button,
+        InputHandler
+    },
+    prelude::*,
+    renderer::VirtualKeyCode
+};
+use crate::{
+    constants,
+    states::Menu
+};
+
+#[derive(Clone, Copy)]
+enum ControlsAction {
+    Back
+}
+
+// Lists the key bindings, read from the loaded bindings so it follows the bindings file
+pub struct ControlsState {
+    menu: Menu<ControlsAction>
+}
+
+impl ControlsState {
+
+    pub fn new() -> Self {
+        return Self {
+            menu: Menu::new()
+                .with_title("Controls")
+                .with_layer(10.0)
+                .with_action("Back", ControlsAction::Back)
+        };
+    }
+
+    fn button_name(button: &Button) -> String {
+        return match button {
+            Button::Key(key) => format!("{:?}", key),
+            _ => format!("{:?}", button)
+        };
+    }
+
+    fn axis_names(input: &InputHandler<String, String>, axis: &str) -> (String, String) {
+        return match input.bindings.axis(axis) {
+            Some(Axis::Emulated { pos, neg }) => (ControlsState::button_name(pos), ControlsState::button_name(neg)),
+            _ => (String::from("-"), String::from("-"))
+        };
+    }
+
+    fn action_names(input: &InputHandler<String, String>, action: &str) -> String {
+        let combinations: Vec<String> = input.bindings
+            .action_bindings(action)
+            .map(|combination| combination
+                .iter()
+                .map(ControlsState::button_name)
+                .collect::<Vec<String>>()
+                .join("+"))
+            .collect();
+        if combinations.is_empty() {
+            return String::from("-");
+        }
+        return combinations.join(" or ");
+    }
+
+    fn get_control_lines(world: &World) -> Vec<String> {
+        let input = world.read_resource::<InputHandler<String, String>>();
+
+        let mut lines: Vec<String> = (0..constants::MAX_PLAYERS)
+            .map(|player_index| {
+                let (right, left) = ControlsState::axis_names(&input, constants::PLAYER_X_AXIS_BINDINGS[player_index]);
+                let (up, down) = ControlsState::axis_names(&input, constants::PLAYER_Y_AXIS_BINDINGS[player_index]);
+                return format!(
+                    "Player {}  Move: {} {} {} {}  Fire: {}",
+                    player_index + 1,
+                    up,
+                    left,
+                    down,
+                    right,
+                    ControlsState::action_names(&input, constants::PLAYER_FIRE_BINDINGS[player_index])
+                );
+            })
+            .collect();
+        lines.push(String::from("Pause: Escape"));
+        lines.push(format!("Fullscreen: {}", ControlsState::action_names(&input, constants::FULLSCREEN_BINDING)));
+        lines.push(format!("Debug overlay: {}", ControlsState::action_names(&input, constants::DEBUG_OVERLAY_BINDING)));
+        lines.push(String::from("Console: ~"));
+
+        return lines;
+    }
+
+}
+
+impl SimpleState for ControlsState {
+
+    fn handle_event(&mut self, data: StateData<GameData>, event: StateEvent) -> SimpleTrans {
+        if let StateEvent::Window(event) = &event {
+            if input::is_key_down(&event, VirtualKeyCode::Escape) {
+                return Trans::Pop;
+            }
+        }
+
+        return match self.menu.handle_event(data.world, &event) {
+            Some(ControlsAction::Back) => Trans::Pop,
+            None => Trans::None
+        };
+    }
+
+    fn on_start(&mut self, data: StateData<GameData>) {
+        self.menu.set_lines(ControlsState::get_control_lines(data.world));
+        self.menu.build(data.world);
+    }
+
+    fn on_stop(&mut self, data: StateData<GameData>) {
+        self.menu.clear(data.world);
+    }
+
+}
@@ -1,52 +1,68 @@
 use amethyst::{
     core::{
-        nalgebra::Vector2,
-        Parent,
         transform::Transform,
+        ParentHierarchy,
         Time
     },
     ecs::{
         Dispatcher,
-        DispatcherBuilder
+        DispatcherBuilder,
+        Entity,
+        Join,
+        System
     },
     input,
     prelude::*,
     renderer::{
-        SpriteRender, 
-        SpriteSheetHandle, 
         Camera,
         Projection,
         VirtualKeyCode
     },
     ui::{
-        Anchor,
+        UiCreator,
+        UiFinder,
         UiImage,
-        UiText,
-        UiTransform
+        UiText
+    },
+    winit::{
+        Event,
+        WindowEvent
     }
 };
 use crate::{
     constants,
     components::{
-        Cannon,
-        Moveable,
-        Rect,
         Killable,
-        SpaceShip,
+        Moveable,
+        Player,
         tags::{
-            BoundInArenaTag,
-            PlayerShipTag
+            BackgroundTag,
+            ViewportAnchorTag
         }
     },
+    prefabs::{
+        Campaign,
+        LevelData,
+        PlayerShipPrefabData,
+        PlayerShipPrefabs,
+        SimplePrefab
+    },
     resources::{
+        AssetRegistry,
+        Console,
+        GameOptions,
         GameplayNextState,
         GameplaySessionData,
+        GameRules,
         GameState,
-        UiAssets,
-        UiGameplayElements
+        SpriteSheets,
+        SystemTimings,
+        UiGameplayElements,
+        UiPlayerElements
     },
     systems,
     states::{
+        LevelCompleteState,
         PauseState,
         ResultState
     }
@@ -54,45 +70,83 @@ use crate::{
 
 pub struct GameplayState {
     dispatcher: Option<Dispatcher<'static, 'static>>,
-    current_state: GameState
+    current_state: GameState,
+    player_ships: Vec<String>,
+    // Index of the campaign level, endless mode when not set
+    level: Option<usize>
 }
 
 impl GameplayState {
     
-    pub fn new() -> Self {
+    // Each player gets the ship prefab with the name at their index
+    pub fn new(player_ships: Vec<String>, level: Option<usize>) -> Self {
         return GameplayState {
             dispatcher: None,
-            current_state: GameState::Running
+            current_state: GameState::Running,
+            player_ships,
+            level
         };
     }
 
+    fn get_level_data(&self, world: &World) -> Option<LevelData> {
+        let level = self.level?;
+        return world.read_resource::<Campaign>().get_level(level).cloned();
+    }
+
 }
 
 impl GameplayState {
 
-    fn initialise_dispatcher(&mut self, world: &mut World) {
+    fn initialise_dispatcher(&mut self, world: &mut World, level_data: Option<LevelData>) {
         let mut dispatcher_builder = DispatcherBuilder::new();
 
-        dispatcher_builder.add(systems::ExpireSystem, "expire", &[]);
-        dispatcher_builder.add(systems::PlayerShipSystem, "player_ship_system", &["expire"]);
-        dispatcher_builder.add(systems::MovementSystem, "movement_system", &["player_ship_system"]);
-        dispatcher_builder.add(systems::ShootingSystem, "shooting_system", &["player_ship_system"]);
-        dispatcher_builder.add(systems::BoundInArenaSystem, "bound_in_arena_system", &["movement_system"]);
-        dispatcher_builder.add(systems::DestroyOutOfArenaSystem, "destroy_out_of_arena_system", &["bound_in_arena_system"]);
-        dispatcher_builder.add(systems::MissileSystem, "missile_system", &["movement_system", "shooting_system"]);
-        dispatcher_builder.add(systems::KillSystem, "kill_system", &["missile_system"]);
-        dispatcher_builder.add(systems::EnemyCollisionSystem, "enemy_collision", &["kill_system"]);
-        dispatcher_builder.add(systems::PickupsSystem, "pickup", &["enemy_collision"]);
-        dispatcher_builder.add(systems::EnemySpawnerSystem::default(), "enemy_spawner", &["destroy_out_of_arena_system"]);
-        dispatcher_builder.add(systems::ClearChildrenSystem, "clear_children", &["pickup", "enemy_collision", "destroy_out_of_arena_system"]);
-        dispatcher_builder.add(systems::DeleteEntitiesSystem, "delete_entities", &["expire", "clear_children"]);
-        dispatcher_builder.add(systems::UiSystem, "ui", &["enemy_collision"]);
+        GameplayState::add_timed_system(&mut dispatcher_builder, systems::ConsoleCommandSystem, "console_commands", &[]);
+        GameplayState::add_timed_system(&mut dispatcher_builder, systems::ExpireSystem, "expire", &[]);
+        GameplayState::add_timed_system(&mut dispatcher_builder, systems::PlayerShipSystem, "player_ship_system", &["expire"]);
+        GameplayState::add_timed_system(&mut dispatcher_builder, systems::MovementSystem, "movement_system", &["player_ship_system"]);
+        GameplayState::add_timed_system(&mut dispatcher_builder, systems::ShootingSystem, "shooting_system", &["player_ship_system"]);
+        GameplayState::add_timed_system(&mut dispatcher_builder, systems::BoundInArenaSystem, "bound_in_arena_system", &["movement_system"]);
+        GameplayState::add_timed_system(&mut dispatcher_builder, systems::DestroyOutOfArenaSystem, "destroy_out_of_arena_system", &["bound_in_arena_system"]);
+        GameplayState::add_timed_system(&mut dispatcher_builder, systems::CollisionGridSystem, "collision_grid", &["bound_in_arena_system", "shooting_system"]);
+        GameplayState::add_timed_system(&mut dispatcher_builder, systems::CollisionSystem::default(), "collision", &["collision_grid"]);
+        GameplayState::add_timed_system(&mut dispatcher_builder, systems::DamageSystem::default(), "damage", &["collision"]);
+        GameplayState::add_timed_system(&mut dispatcher_builder, systems::PickupsSystem::default(), "pickup", &["collision"]);
+        GameplayState::add_timed_system(&mut dispatcher_builder, systems::PhysicsSystem::default(), "physics", &["collision"]);
+        GameplayState::add_timed_system(&mut dispatcher_builder, systems::RegenerationSystem, "regeneration", &["damage"]);
+        GameplayState::add_timed_system(&mut dispatcher_builder, systems::KillSystem, "kill_system", &["damage", "regeneration"]);
+        GameplayState::add_timed_system(&mut dispatcher_builder, systems::DebrisSystem, "debris", &["kill_system", "physics"]);
+        // Campaign levels replace the endless spawner with their own waves
+        match level_data {
+            Some(level_data) => {
+                GameplayState::add_timed_system(&mut dispatcher_builder, systems::LevelSystem::new(level_data), "level", &["destroy_out_of_arena_system", "kill_system"]);
+            },
+            None => {
+                GameplayState::add_timed_system(&mut dispatcher_builder, systems::EnemySpawnerSystem::default(), "enemy_spawner", &["destroy_out_of_arena_system"]);
+            }
+        }
+        GameplayState::add_timed_system(&mut dispatcher_builder, systems::DeleteEntitiesSystem, "delete_entities", &["expire", "pickup", "kill_system", "destroy_out_of_arena_system", "debris"]);
+        GameplayState::add_timed_system(&mut dispatcher_builder, systems::UiSystem, "ui", &["kill_system", "pickup"]);
+        GameplayState::add_timed_system(&mut dispatcher_builder, systems::HealthBarSystem, "health_bar", &["kill_system", "pickup"]);
+        if cfg!(debug_assertions) {
+            GameplayState::add_timed_system(&mut dispatcher_builder, systems::LifecycleAuditSystem::default(), "lifecycle_audit", &["delete_entities", "ui", "health_bar"]);
+        }
 
+        world.add_resource(SystemTimings::default());
         let mut dispatcher = dispatcher_builder.build();
         dispatcher.setup(&mut world.res);
         self.dispatcher = Some(dispatcher);
     }
 
+    // Gameplay systems are timed for the debug overlay
+    fn add_timed_system<S>(
+        dispatcher_builder: &mut DispatcherBuilder<'static, 'static>,
+        system: S,
+        name: &'static str,
+        dependencies: &[&str]
+    ) where S: for<'c> System<'c> + Send + 'static {
+        dispatcher_builder.add(systems::TimedSystem::new(name, system), name, dependencies);
+    }
+
     fn terminate_dispatcher(&mut self) {
         self.dispatcher = None;
     }
@@ -101,209 +155,166 @@ impl GameplayState {
         world.delete_all();
     }
 
-    fn initialise_player_ship(world: &mut World) {
-        let mut transform: Transform = Transform::default();
-
-        let player_scale = 0.4;
-        let player_width = 99.0 * player_scale;
-        let player_height = 75.0 * player_scale;
-
-        let missile_x_offset = 0.0;
-        let missile_y_offset = player_height / 2.0;
-        let missile_width = 9.0 * player_scale;
-        let missile_height = 37.0 * player_scale;
-
-        let x = constants::ARENA_WIDTH / 2.0;
-        let y = player_height / 2.0;
-        transform.set_xyz(x, y, 0.0);
-        transform.set_scale(player_scale, player_scale, 1.0);
-
-        let sprite_render = {
-            let sprite_sheet = world.read_resource::<SpriteSheetHandle>();
-
-            SpriteRender {
-                sprite_sheet: sprite_sheet.clone(),
-                sprite_number: 0
-            }
+    fn initialise_player_ship(world: &mut World, player_index: usize, player_count: usize, ship_name: &str) {
+        let game_rules = world.read_resource::<GameRules>().clone();
+        let player_entity = {
+            let player_ship_prefabs = world.read_resource::<PlayerShipPrefabs>();
+            let prefab = player_ship_prefabs
+                .get(ship_name)
+                .or_else(|| player_ship_prefabs.get(constants::DEFAULT_PLAYER_SHIP))
+                .expect("initialise_player_ship, default player ship prefab was not loaded!");
+
+            // Spread players evenly across the bottom of the arena
+            let x = game_rules.arena_width * (player_index + 1) as f32 / (player_count + 1) as f32;
+            let y = prefab.height / 2.0;
+
+            let entities = world.entities();
+            let mut system_data: <PlayerShipPrefabData as SimplePrefab>::SystemData = world.system_data();
+            prefab.create_entity(&entities, x, y, &mut system_data)
         };
 
-        let player_entity = world
-            .create_entity()
-            .with(sprite_render)
-            .with(transform)
-            .with(PlayerShipTag)
-            .with(BoundInArenaTag)
-            .with(Rect {
-                width: player_width,
-                height: player_height
-            })
-            .with(Moveable {
-                move_speed: 250.0,
-                direction: Vector2::new(0.0, 0.0)
-            })
-            .with(Killable::new_player(5, 3))
-            .with(SpaceShip {
-                is_attacking: false,
-                cannon_entities_indices: Vec::new()
-            })
-            .build();
-        let cannon_entity = world
-            .create_entity()
-            .with(Parent {
-                entity: player_entity
-            })
-            .with(Cannon {
-                x_offset: missile_x_offset,
-                y_offset: missile_y_offset,
-                attack_cooldown: 0.5,
-                last_attack_time: 0.0,
-                missile_width: missile_width,
-                missile_height: missile_height,
-                missile_speed: 500.0,
-                missile_sprite_index: 13
-            })
-            .build();
+        world
+            .write_storage::<Player>()
+            .insert(player_entity, Player::new(player_index))
+            .expect("Could not create Player!");
 
-        let mut space_ship_storage = world.write_storage::<SpaceShip>();
-        let player_space_ship = space_ship_storage.get_mut(player_entity).unwrap();
-        player_space_ship.cannon_entities_indices.push(cannon_entity.id());
+        // Game rules adjust every ship on top of its prefab stats
+        if let Some(moveable) = world.write_storage::<Moveable>().get_mut(player_entity) {
+            moveable.move_speed *= game_rules.player_movement_speed_multiplier;
+        }
+        if let Some(killable) = world.write_storage::<Killable>().get_mut(player_entity) {
+            killable.add_bonus_health(game_rules.player_bonus_health);
+        }
     }
 
     fn initialise_camera(world: &mut World) {
+        let (arena_width, arena_height) = {
+            let game_rules = world.read_resource::<GameRules>();
+            (game_rules.arena_width, game_rules.arena_height)
+        };
         let mut transform: Transform = Transform::default();
         transform.set_z(1.0);
         world
             .create_entity()
             .with(Camera::from(Projection::orthographic(
                 0.0, 
-                constants::ARENA_WIDTH,
+                arena_width,
                 0.0,
-                constants::ARENA_HEIGHT
+                arena_height
             )))
             .with(transform)
             .build();
     }
 
-    fn initialise_gameplay_session_data(world: &mut World) {
-        let session_data = GameplaySessionData{ score: 0 };
-        world.add_resource(session_data);
-    }
-
-    fn initialise_ui(world: &mut World) {
-        let (font, life_img) = {
-            let ui_assets = world.read_resource::<UiAssets>();
-            (ui_assets.get_font(), ui_assets.get_life_img())
+    fn initialise_background(world: &mut World, level_data: &LevelData) {
+        let background = match &level_data.background {
+            Some(background) => background,
+            None => return
+        };
+        let sprite_render = world
+            .read_resource::<SpriteSheets>()
+            .get_sprite_render(background)
+            .expect("Level backgrounds should have been validated while loading!");
+        let (arena_width, arena_height) = {
+            let game_rules = world.read_resource::<GameRules>();
+            (game_rules.arena_width, game_rules.arena_height)
         };
 
-        // Initialise score
-        let score_text = UiText::new(
-            font.clone(),
-            String::from("Score:"),
-            constants::UI_FONT_COLOR,
-            constants::UI_GAMEPLAY_FONT_SIZE
-        );
-        let score_text_transform = UiTransform::new(
-            String::from("score_txt"),
-            Anchor::TopRight,
-            -100.0,
-            -constants::UI_GAMEPLAY_FONT_SIZE,
-            1.0,
-            80.0,
-            constants::UI_GAMEPLAY_FONT_SIZE,
-            1
+        // No system reads the tag outside of debug builds
+        world.register::<BackgroundTag>();
+
+        // Behind everything else in the arena
+        let mut transform = Transform::default();
+        transform.set_xyz(arena_width / 2.0, arena_height / 2.0, -0.5);
+        transform.set_scale(
+            arena_width / constants::BACKGROUND_SPRITE_WIDTH,
+            arena_height / constants::BACKGROUND_SPRITE_HEIGHT,
+            1.0
         );
         world
             .create_entity()
-            .with(score_text)
-            .with(score_text_transform)
+            .with(sprite_render)
+            .with(transform)
+            .with(BackgroundTag)
             .build();
+    }
 
-        let score_value_text = UiText::new(
-            font.clone(),
-            String::from("0"),
-            constants::UI_FONT_COLOR,
-            constants::UI_GAMEPLAY_FONT_SIZE
-        );
-        let score_value_text_transform = UiTransform::new(
-            String::from("score_value_txt"),
-            Anchor::TopRight,
-            -40.0,
-            -constants::UI_GAMEPLAY_FONT_SIZE,
-            1.0,
-            40.0,
-            constants::UI_GAMEPLAY_FONT_SIZE,
-            1
-        );
-        let score_value_text = world
-            .create_entity()
-            .with(score_value_text)
-            .with(score_value_text_transform)
-            .build();
-        // Initialise Life
-        let life_image = UiImage {
-            texture: life_img
+    fn initialise_gameplay_session_data(world: &mut World, player_count: usize) {
+        let session_data = GameplaySessionData::new(player_count);
+        world.write_resource::<Time>().set_time_scale(session_data.get_time_scale());
+        world.add_resource(session_data);
+    }
+
+    fn initialise_ui(world: &mut World, player_count: usize) {
+        // Layouts are loaded asynchronously, elements are looked up once they exist
+        let hud_roots: Vec<Entity> = world.exec(|mut creator: UiCreator| {
+            return constants::PLAYER_HUD_LAYOUTS
+                .iter()
+                .take(player_count)
+                .map(|layout| creator.create(*layout, ()))
+                .collect();
+        });
+
+        // HUD corners follow the arena rather than the window
+        for hud_root in hud_roots {
+            world
+                .write_storage::<ViewportAnchorTag>()
+                .insert(hud_root, ViewportAnchorTag)
+                .expect("Could not create ViewportAnchorTag!");
+        }
+
+        world.add_resource(UiGameplayElements::new(Vec::new()));
+    }
+
+    fn find_ui_elements(world: &mut World, player_count: usize) {
+        if world.read_resource::<UiGameplayElements>().players.len() == player_count {
+            return;
+        }
+
+        let ui_players_elements: Option<Vec<(UiPlayerElements, Entity)>> = world.exec(|finder: UiFinder| {
+            return (1..=player_count)
+                .map(|player_number| {
+                    let score_value_text = finder.find(&format!("score_value_txt_p{}", player_number))?;
+                    let life_value_text = finder.find(&format!("life_value_txt_p{}", player_number))?;
+                    let life_image = finder.find(&format!("life_img_p{}", player_number))?;
+                    return Some((UiPlayerElements::new(score_value_text, life_value_text), life_image));
+                })
+                .collect();
+        });
+
+        if let Some(ui_players_elements) = ui_players_elements {
+            let (ui_players_elements, life_images): (Vec<UiPlayerElements>, Vec<Entity>) = ui_players_elements
+                .into_iter()
+                .unzip();
+            GameplayState::apply_hud_assets(world, &life_images);
+            world.add_resource(UiGameplayElements::new(ui_players_elements));
+        }
+    }
+
+    // Layouts can't refer to the asset manifest, so the HUD font and images are set once loaded
+    fn apply_hud_assets(world: &mut World, life_images: &[Entity]) {
+        let (font, life_texture) = {
+            let asset_registry = world.read_resource::<AssetRegistry>();
+            (asset_registry.get_font(constants::UI_FONT), asset_registry.get_texture(constants::HUD_LIFE_TEXTURE))
         };
-        let life_image_transform = UiTransform::new(
-            String::from("life_img"),
-            Anchor::BottomLeft,
-            25.0,
-            25.0,
-            1.0,
-            16.0,
-            16.0,
-            1
-        );
-        world
-            .create_entity()
-            .with(life_image)
-            .with(life_image_transform)
-            .build();
 
-        let life_text = UiText::new(
-            font.clone(),
-            String::from("x"),
-            constants::UI_FONT_COLOR,
-            constants::UI_GAMEPLAY_FONT_SIZE
-        );
-        let life_text_transform = UiTransform::new(
-            String::from("life_txt"),
-            Anchor::BottomLeft,
-            41.0,
-            25.0,
-            1.0,
-            8.0,
-            8.0,
-            1
-        );
-        world
-            .create_entity()
-            .with(life_text)
-            .with(life_text_transform)
-            .build();
-        
-        let life_value_text = UiText::new(
-            font.clone(),
-            constants::PLAYER_LIVES.to_string(),
-            constants::UI_FONT_COLOR,
-            constants::UI_GAMEPLAY_FONT_SIZE
-        );
-        let life_value_text_transform = UiTransform::new(
-            String::from("life_value_txt"),
-            Anchor::BottomLeft,
-            55.0,
-            25.0,
-            1.0,
-            33.0,
-            26.0,
-            1
-        );
-        let life_value_text = world
-            .create_entity()
-            .with(life_value_text)
-            .with(life_value_text_transform)
-            .build();
+        let hierarchy = world.read_resource::<ParentHierarchy>();
+        let viewport_anchor_tags = world.read_storage::<ViewportAnchorTag>();
+        let mut ui_texts = world.write_storage::<UiText>();
+        for (hud_root, _) in (&world.entities(), &viewport_anchor_tags).join() {
+            for (_, ui_text) in (hierarchy.all_children(hud_root), &mut ui_texts).join() {
+                ui_text.font = font.clone();
+            }
+        }
 
-        world.add_resource(UiGameplayElements::new(score_value_text, life_value_text));
+        let mut ui_images = world.write_storage::<UiImage>();
+        for life_image in life_images {
+            ui_images
+                .insert(*life_image, UiImage {
+                    texture: life_texture.clone()
+                })
+                .expect("Could not create UiImage!");
+        }
     }
 
     fn initialise_gameplay_resources(world: &mut World) {
@@ -316,12 +327,19 @@ impl SimpleState for GameplayState {
 
 	fn on_start(&mut self, data: StateData<GameData>) {
         let world = data.world;
-        self.initialise_dispatcher(world);
+        let level_data = self.get_level_data(world);
+        self.initialise_dispatcher(world, level_data.clone());
+        if let Some(level_data) = &level_data {
+            GameplayState::initialise_background(world, level_data);
+        }
         
-        GameplayState::initialise_player_ship(world);
+        let player_count = self.player_ships.len();
+        for (player_index, ship_name) in self.player_ships.iter().enumerate() {
+            GameplayState::initialise_player_ship(world, player_index, player_count, ship_name);
+        }
         GameplayState::initialise_camera(world);
-        GameplayState::initialise_gameplay_session_data(world);
-        GameplayState::initialise_ui(world);
+        GameplayState::initialise_gameplay_session_data(world, player_count);
+        GameplayState::initialise_ui(world, player_count);
         GameplayState::initialise_gameplay_resources(world);
     }
 
@@ -346,20 +364,35 @@ impl SimpleState for GameplayState {
         let mut gameplay_next_state = world.write_resource::<GameplayNextState>();
         gameplay_next_state.next_state = Some(GameState::Running);
 
+        // Keeps the scale set from the console
+        let time_scale = world.read_resource::<GameplaySessionData>().get_time_scale();
         let mut time = world.write_resource::<Time>();
-        time.set_time_scale(1.0);
+        time.set_time_scale(time_scale);
     }
 
-    fn handle_event(&mut self, _: StateData<GameData>, event: StateEvent) -> SimpleTrans {
+    fn handle_event(&mut self, data: StateData<GameData>, event: StateEvent) -> SimpleTrans {
         if let StateEvent::Window(event) = &event {
-            if input::is_key_down(&event, VirtualKeyCode::Escape) {
-                return Trans::Push(Box::new(PauseState::new()));
+            let is_console_open = data.world.read_resource::<Console>().is_open;
+            if input::is_key_down(&event, VirtualKeyCode::Escape) && !is_console_open {
+                return Trans::Push(Box::new(PauseState::new(self.player_ships.clone(), self.level)));
+            }
+
+            if let Event::WindowEvent { event: WindowEvent::Focused(false), .. } = event {
+                if data.world.read_resource::<GameOptions>().is_pause_on_focus_lost {
+                    return Trans::Push(Box::new(PauseState::new(self.player_ships.clone(), self.level)));
+                }
             }
         }
 
         return Trans::None;
     }
 
+    fn update(&mut self, data: &mut StateData<GameData>) -> SimpleTrans {
+        GameplayState::find_ui_elements(data.world, self.player_ships.len());
+
+        return Trans::None;
+    }
+
     fn fixed_update(&mut self, data: /*&mut*/ StateData<GameData>) -> SimpleTrans {
         {
             // Change state
@@ -371,12 +404,24 @@ impl SimpleState for GameplayState {
         }
         
         match self.current_state {
-            GameState::Running => self.dispatcher.as_mut().unwrap().dispatch(&data.world.res),
+            GameState::Running => {
+                let delta_seconds = {
+                    let time = data.world.read_resource::<Time>();
+                    time.fixed_seconds() * time.time_scale()
+                };
+                data.world.write_resource::<GameplaySessionData>().add_elapsed_time(delta_seconds);
+                self.dispatcher.as_mut().unwrap().dispatch(&data.world.res);
+            },
             GameState::Paused => {
                 // Do nothing
             },
             GameState::Finished => {
-                return Trans::Push(Box::new(ResultState::new()));
+                return Trans::Push(Box::new(ResultState::new(self.player_ships.clone(), self.level)));
+            },
+            GameState::LevelComplete => {
+                if let Some(level) = self.level {
+                    return Trans::Push(Box::new(LevelCompleteState::new(self.player_ships.clone(), level)));
+                }
             }
         };
 
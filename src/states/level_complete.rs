@@ -0,0 +1,109 @@
+use amethyst::{
+    prelude::*,
+    shrev::EventChannel
+};
+use log::error;
+use crate::{
+    prefabs::Campaign,
+    resources::{
+        CampaignSave,
+        GameplaySessionData
+    },
+    states::{
+        GameplayState,
+        Menu
+    }
+};
+
+#[derive(Clone, Copy)]
+enum LevelCompleteAction {
+    NextLevel,
+    LevelSelect
+}
+
+pub struct LevelCompleteState {
+    menu: Menu<LevelCompleteAction>,
+    player_ships: Vec<String>,
+    level: usize
+}
+
+impl LevelCompleteState {
+
+    pub fn new(player_ships: Vec<String>, level: usize) -> Self {
+        return Self {
+            menu: Menu::new(),
+            player_ships,
+            level
+        };
+    }
+
+    // The last level has nothing to continue to
+    fn create_menu(&self, world: &World) -> Menu<LevelCompleteAction> {
+        let level_count = world.read_resource::<Campaign>().get_levels().len();
+        if self.level + 1 < level_count {
+            return Menu::new()
+                .with_title("Level Complete")
+                .with_layer(10.0)
+                .with_action("Next Level", LevelCompleteAction::NextLevel)
+                .with_action("Level Select", LevelCompleteAction::LevelSelect);
+        }
+        return Menu::new()
+            .with_title("Campaign Complete")
+            .with_layer(10.0)
+            .with_action("Level Select", LevelCompleteAction::LevelSelect);
+    }
+
+    fn unlock_next_level(&self) {
+        let mut campaign_save = CampaignSave::load();
+        campaign_save.unlock(self.level + 1);
+        if let Err(err) = campaign_save.save() {
+            error!("Could not save campaign progress: {}", err);
+        }
+    }
+
+    fn get_stats_lines(world: &World) -> Vec<String> {
+        let session_data = world.read_resource::<GameplaySessionData>();
+        let elapsed_seconds = session_data.get_elapsed_time() as u32;
+        return vec![
+            format!("Time {:02}:{:02}", elapsed_seconds / 60, elapsed_seconds % 60),
+            format!("Score {}", session_data.get_total_score()),
+            format!("Enemies destroyed {}", session_data.get_enemies_destroyed())
+        ];
+    }
+
+}
+
+impl SimpleState for LevelCompleteState {
+
+    fn handle_event(&mut self, data: StateData<GameData>, event: StateEvent) -> SimpleTrans {
+        match self.menu.handle_event(data.world, &event) {
+            Some(LevelCompleteAction::NextLevel) => {
+                let (player_ships, level) = (self.player_ships.clone(), self.level + 1);
+                let mut event_channel = data.world.write_resource::<EventChannel<TransEvent<GameData, StateEvent>>>();
+                event_channel.single_write(Box::new(|| Trans::Pop));
+                event_channel.single_write(Box::new(|| Trans::Pop));
+                event_channel.single_write(Box::new(move || Trans::Push(Box::new(GameplayState::new(player_ships, Some(level))))));
+            },
+            Some(LevelCompleteAction::LevelSelect) => {
+                let mut event_channel = data.world.write_resource::<EventChannel<TransEvent<GameData, StateEvent>>>();
+                event_channel.single_write(Box::new(|| Trans::Pop));
+                event_channel.single_write(Box::new(|| Trans::Pop));
+            },
+            None => {}
+        }
+
+        return Trans::None;
+    }
+
+    fn on_start(&mut self, data: StateData<GameData>) {
+        self.unlock_next_level();
+        self.menu = self.create_menu(data.world);
+        self.menu.set_lines(LevelCompleteState::get_stats_lines(data.world));
+        self.menu.build(data.world);
+    }
+
+    fn on_stop(&mut self, data: StateData<GameData>) {
+        self.menu.clear(data.world);
+    }
+
+}
@@ -0,0 +1,106 @@
+use amethyst::{
+    prelude::*,
+    renderer::VirtualKeyCode,
+    input
+};
+use crate::{
+    prefabs::Campaign,
+    resources::CampaignSave,
+    states::{
+        Menu,
+        ShipSelectState
+    }
+};
+
+#[derive(Clone, Copy)]
+enum LevelSelectAction {
+    Level(usize),
+    Back
+}
+
+pub struct LevelSelectState {
+    menu: Menu<LevelSelectAction>,
+    campaign_save: CampaignSave
+}
+
+impl LevelSelectState {
+
+    pub fn new() -> Self {
+        return Self {
+            menu: Menu::new(),
+            campaign_save: CampaignSave::default()
+        };
+    }
+
+    // Levels are added once the campaign resource is available
+    fn create_menu(world: &World) -> Menu<LevelSelectAction> {
+        let level_count = world.read_resource::<Campaign>().get_levels().len();
+        return (0..level_count)
+            .fold(
+                Menu::new().with_title("Campaign"),
+                |menu, level_index| menu.with_action("", LevelSelectAction::Level(level_index))
+            )
+            .with_action("Back", LevelSelectAction::Back);
+    }
+
+    // Progress is read again every time, a finished level unlocks the next one
+    fn build_menu(&mut self, world: &mut World) {
+        self.campaign_save = CampaignSave::load();
+        let labels: Vec<String> = world.read_resource::<Campaign>()
+            .get_levels()
+            .iter()
+            .enumerate()
+            .map(|(level_index, level)| {
+                if self.campaign_save.is_unlocked(level_index) {
+                    return format!("{}. {}", level_index + 1, level.name);
+                }
+                return String::from("Locked");
+            })
+            .collect();
+        for (level_index, label) in labels.iter().enumerate() {
+            self.menu.set_label(level_index, label);
+        }
+        self.menu.build(world);
+    }
+
+}
+
+impl SimpleState for LevelSelectState {
+
+    fn handle_event(&mut self, data: StateData<GameData>, event: StateEvent) -> SimpleTrans {
+        if let StateEvent::Window(event) = &event {
+            if input::is_key_down(&event, VirtualKeyCode::Escape) {
+                return Trans::Pop;
+            }
+        }
+
+        return match self.menu.handle_event(data.world, &event) {
+            Some(LevelSelectAction::Level(level_index)) => {
+                if !self.campaign_save.is_unlocked(level_index) {
+                    return Trans::None;
+                }
+                Trans::Push(Box::new(ShipSelectState::new(1).with_level(level_index)))
+            },
+            Some(LevelSelectAction::Back) => Trans::Pop,
+            None => Trans::None
+        };
+    }
+
+    fn on_start(&mut self, data: StateData<GameData>) {
+        self.menu = LevelSelectState::create_menu(data.world);
+        self.build_menu(data.world);
+    }
+
+    fn on_stop(&mut self, data: StateData<GameData>) {
+        self.menu.clear(data.world);
+    }
+
+    fn on_pause(&mut self, data: StateData<GameData>) {
+        self.menu.clear(data.world);
+    }
+
+    fn on_resume(&mut self, data: StateData<GameData>) {
+        self.build_menu(data.world);
+    }
+
+}
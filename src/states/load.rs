@@ -1,204 +1,245 @@
-use std::fs;
-use serde_json;
+use std::{
+    collections::HashMap,
+    sync::mpsc::{
+        self,
+        Receiver,
+        TryRecvError
+    },
+    thread
+};
 use amethyst::{
     assets::{
         AssetStorage,
-        Handle,
         Loader,
-        Prefab
+        ProgressCounter
     },
+    config::Config,
     ecs::Entity,
     prelude::*,
     renderer::{
-        PngFormat,
-        SpriteSheet,
-        SpriteSheetFormat,
+        ScreenDimensions,
         Texture,
-        TextureMetadata
+        TextureData
     },
     ui::{
         Anchor,
-        FontAsset,
-        TtfFormat,
+        UiImage,
         UiText,
         UiTransform
-    }
+    },
+    utils::application_root_dir
 };
 use crate::{
     constants,
     prefabs::{
-        EnemyPrefabData,
+        self,
+        Campaign,
         EnemyPrefabs,
-        SimplePrefab
+        PlayerShipPrefabs,
+        PrefabError,
+        load_sprite_name_map
+    },
+    resources::{
+        AssetManifest,
+        AssetRegistry,
+        CollisionMatrix,
+        GameRules,
+        MenuLayouts,
+        SpriteNames,
+        SpriteRef,
+        SpriteSheetManifest,
+        SpriteSheets
     },
-    resources::UiAssets,
     states::MainMenuState
 };
+use log::error;
+
+const ASSET_MANIFEST_PATH: &str = "assets/config/asset_manifest.ron";
+const MENU_LAYOUTS_PATH: &str = "assets/ui/layouts/menus.ron";
+// Loading view is shown before any layout is loaded, so it keeps its own sizes
+const LOADING_TEXT_WIDTH: f32 = 256.0;
+const LOADING_TEXT_HEIGHT: f32 = 64.0;
+const LOADING_FONT_SIZE: f32 = 32.0;
+const PROGRESS_BAR_WIDTH: f32 = 400.0;
+const PROGRESS_BAR_HEIGHT: f32 = 12.0;
+const PROGRESS_BAR_BACKGROUND_COLOR: [f32; 4] = [0.2, 0.2, 0.2, 1.0];
+
+// Result of the prefab loading thread
+struct LoadedPrefabs {
+    sprite_names: SpriteNames,
+    enemy_prefabs: EnemyPrefabs,
+    player_ship_prefabs: PlayerShipPrefabs,
+    campaign: Campaign,
+    errors: Vec<PrefabError>
+}
 
 pub struct LoadingState {
+    progress_counter: ProgressCounter,
+    prefabs_receiver: Option<Receiver<LoadedPrefabs>>,
+    prefab_errors: Vec<PrefabError>,
+    asset_errors: Vec<String>,
     loading_text: Option<Entity>,
-    load_complete: bool
+    progress_bar: Option<Entity>,
+    progress_bar_background: Option<Entity>,
+    error_texts: Vec<Entity>,
+    load_failed: bool
 }
 
 impl LoadingState {
 
     pub fn new() -> Self {
         return LoadingState {
+            progress_counter: ProgressCounter::new(),
+            prefabs_receiver: None,
+            prefab_errors: Vec::new(),
+            asset_errors: Vec::new(),
             loading_text: None,
-            load_complete: false
+            progress_bar: None,
+            progress_bar_background: None,
+            error_texts: Vec::new(),
+            load_failed: false
         };
     }
 
     fn load_assets(&mut self, world: &mut World) {
-        self.load_ui_assets(world);
-        self.load_sprite_sheet(world);
-        self.load_prefabs(world);
+        let manifest = self.load_asset_manifest();
+        self.load_asset_registry(world, &manifest);
+        self.load_menu_layouts(world, &manifest);
+        self.show_loading_view(world);
 
-        self.load_complete = true;
+        self.load_prefabs(manifest.sprite_sheets);
+        self.load_collision_matrix(world);
     }
 
-    fn load_sprite_sheet(&mut self, world: &mut World) {
-        let texture_handle = {
-            let loader = world.read_resource::<Loader>();
-            let texture_storage = world.read_resource::<AssetStorage<Texture>>();
-
-            loader.load(
-                "assets/sprites/spritesheet.png",
-                PngFormat,
-                TextureMetadata::srgb_scale(),
-                (),
-                &texture_storage
-            )
-        };
+    fn load_asset_manifest(&mut self) -> AssetManifest {
+        let path = format!("{}/{}", application_root_dir(), ASSET_MANIFEST_PATH);
+        return AssetManifest::load_no_fallback(&path).unwrap_or_else(|err| {
+            self.asset_errors.push(format!("{}: {}", ASSET_MANIFEST_PATH, err));
+            AssetManifest::default()
+        });
+    }
 
-        let sprite_sheet_handle = {
-            let loader = world.read_resource::<Loader>();
-            let sprite_sheet_store = world.read_resource::<AssetStorage<SpriteSheet>>();
-
-            loader.load(
-                "assets/sprites/spritesheet.ron",
-                SpriteSheetFormat,
-                texture_handle,
-                (),
-                &sprite_sheet_store
-            )
-        };
+    fn load_asset_registry(&mut self, world: &mut World, manifest: &AssetManifest) {
+        let (asset_registry, errors) = AssetRegistry::load(manifest, world, &mut self.progress_counter);
+        self.asset_errors.extend(errors);
+        world.add_resource(asset_registry);
 
-        world.add_resource(sprite_sheet_handle);
+        // The registry panics on missing names, so entries used by the code are checked up front
+        for font in constants::REQUIRED_FONTS.iter().filter(|font| !manifest.fonts.contains_key(**font)) {
+            self.asset_errors.push(format!("{}: font \"{}\" is required by the game", ASSET_MANIFEST_PATH, font));
+        }
+        for texture in constants::REQUIRED_TEXTURES.iter().filter(|texture| !manifest.textures.contains_key(**texture)) {
+            self.asset_errors.push(format!("{}: texture \"{}\" is required by the game", ASSET_MANIFEST_PATH, texture));
+        }
     }
 
-    fn load_ui_assets(&mut self, world: &mut World) {
-        let font = self.load_font(world);
-        self.show_loading_view(world, font.clone());
-        let button_img = self.load_btn_img(world);
-        let button_hover_img = self.load_btn_hover_img(world);
-        let life_img = self.load_life_img(world);
-        let health_bar_border_img = self.load_health_bar_border_img(world);
-        let health_bar_green_img = self.load_health_bar_green_img(world);
-        let health_bar_red_img = self.load_health_bar_red_img(world);
-
-        let ui_assets = UiAssets::new(
-            font,
-            button_img,
-            button_hover_img,
-            life_img,
-            health_bar_border_img,
-            health_bar_green_img,
-            health_bar_red_img
-        );
-        world.add_resource(ui_assets);
+    fn load_menu_layouts(&mut self, world: &mut World, manifest: &AssetManifest) {
+        let path = format!("{}/{}", application_root_dir(), MENU_LAYOUTS_PATH);
+        let menu_layouts = MenuLayouts::load_no_fallback(&path).unwrap_or_else(|err| {
+            self.asset_errors.push(format!("{}: {}", MENU_LAYOUTS_PATH, err));
+            MenuLayouts::default()
+        });
+        self.asset_errors.extend(menu_layouts
+            .validate(&constants::REQUIRED_MENU_LAYOUTS, manifest)
+            .into_iter()
+            .map(|error| format!("{}: {}", MENU_LAYOUTS_PATH, error)));
+        world.add_resource(menu_layouts);
     }
 
-    fn load_font(&mut self, world: &mut World) -> Handle<FontAsset> {
-        return world
-            .read_resource::<Loader>()
-            .load(
-                "assets/ui/Recharge.ttf",
-                TtfFormat,
-                Default::default(),
-                (),
-                &world.read_resource()
-            );
+    fn is_prefabs_loaded(&self) -> bool {
+        return self.prefabs_receiver.is_none();
     }
 
-    fn load_btn_img(&mut self, world: &mut World) -> Handle<Texture> {
-        return world
-            .read_resource::<Loader>()
-            .load(
-                "assets/ui/button.png",
-                PngFormat,
-                TextureMetadata::srgb_scale(),
-                (),
-                &world.read_resource::<AssetStorage<Texture>>()
-            );
-    }
+    // Checks if the prefab loading thread is done without blocking
+    fn receive_prefabs(&mut self, world: &mut World) {
+        let loaded_prefabs = match &self.prefabs_receiver {
+            Some(receiver) => match receiver.try_recv() {
+                Ok(loaded_prefabs) => loaded_prefabs,
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => LoadedPrefabs {
+                    sprite_names: SpriteNames::default(),
+                    enemy_prefabs: EnemyPrefabs::default(),
+                    player_ship_prefabs: PlayerShipPrefabs::default(),
+                    campaign: Campaign::default(),
+                    errors: vec![PrefabError::new("assets/prefabs", String::from("prefab loading stopped unexpectedly"))]
+                }
+            },
+            None => return
+        };
+        self.prefabs_receiver = None;
 
-    fn load_btn_hover_img(&mut self, world: &mut World) -> Handle<Texture> {
-         return world
-            .read_resource::<Loader>()
-            .load(
-                "assets/ui/button_hover.png",
-                PngFormat,
-                TextureMetadata::srgb_scale(),
-                (),
-                &world.read_resource::<AssetStorage<Texture>>()
-            );
+        let sprite_sheets = SpriteSheets::new(
+            loaded_prefabs.sprite_names,
+            world.read_resource::<AssetRegistry>().get_sprite_sheets()
+        );
+
+        world.add_resource(sprite_sheets);
+        world.add_resource(loaded_prefabs.enemy_prefabs);
+        world.add_resource(loaded_prefabs.player_ship_prefabs);
+        world.add_resource(loaded_prefabs.campaign);
+        self.prefab_errors = loaded_prefabs.errors;
+        prefabs::check_spawnable_category(
+            &world.read_resource::<EnemyPrefabs>(),
+            &world.read_resource::<GameRules>().enemy_category_weights,
+            &mut self.prefab_errors
+        );
     }
 
-    fn load_life_img(&mut self, world: &mut World) -> Handle<Texture> {
-        return world
-            .read_resource::<Loader>()
-            .load(
-                "assets/ui/life.png",
-                PngFormat,
-                TextureMetadata::srgb_scale(),
-                (),
-                &world.read_resource::<AssetStorage<Texture>>()
-            );
+    // Prefabs count as a single step, next to every asset tracked by the progress counter
+    fn get_progress(&self) -> f32 {
+        let total_steps = self.progress_counter.num_assets() + 1;
+        let mut finished_steps = self.progress_counter.num_assets() - self.progress_counter.num_loading();
+        if self.is_prefabs_loaded() {
+            finished_steps += 1;
+        }
+        return finished_steps as f32 / total_steps as f32;
     }
 
-    fn load_health_bar_border_img(&mut self, world: &mut World) -> Handle<Texture> {
-        return world
-            .read_resource::<Loader>()
-            .load(
-                "assets/ui/health_bar_border.png",
-                PngFormat,
-                TextureMetadata::srgb_scale(),
-                (),
-                &world.read_resource::<AssetStorage<Texture>>()
-            );
+    fn update_progress_bar(&self, world: &mut World) {
+        if let Some(progress_bar) = self.progress_bar {
+            if let Some(ui_transform) = world.write_storage::<UiTransform>().get_mut(progress_bar) {
+                let width = PROGRESS_BAR_WIDTH * self.get_progress();
+                // Keep the bar aligned to the left edge of the background
+                ui_transform.width = width;
+                ui_transform.local_x = (width - PROGRESS_BAR_WIDTH) / 2.0;
+            }
+        }
     }
 
-    fn load_health_bar_green_img(&mut self, world: &mut World) -> Handle<Texture> {
-        return world
-            .read_resource::<Loader>()
-            .load(
-                "assets/ui/health_bar_green.png",
-                PngFormat,
-                TextureMetadata::srgb_scale(),
-                (),
-                &world.read_resource::<AssetStorage<Texture>>()
-            );
+    fn collect_load_errors(&mut self) -> Vec<String> {
+        let mut errors: Vec<String> = self.progress_counter
+            .errors()
+            .iter()
+            .map(|asset_error| format!("{}: {}", asset_error.asset_name, asset_error.error))
+            .collect();
+        errors.extend(self.asset_errors.drain(..));
+        errors.extend(self.prefab_errors.drain(..).map(|prefab_error| prefab_error.to_string()));
+        return errors;
     }
 
-    fn load_health_bar_red_img(&mut self, world: &mut World) -> Handle<Texture> {
-        return world
-            .read_resource::<Loader>()
-            .load(
-                "assets/ui/health_bar_red.png",
-                PngFormat,
-                TextureMetadata::srgb_scale(),
-                (),
-                &world.read_resource::<AssetStorage<Texture>>()
-            );
+    fn load_collision_matrix(&mut self, world: &mut World) {
+        let path = format!(
+            "{}/assets/config/collision_matrix.ron",
+            application_root_dir()
+        );
+        world.add_resource(CollisionMatrix::load(&path));
     }
 
-    fn show_loading_view(&mut self, world: &mut World, font: Handle<FontAsset>) {
+    fn show_loading_view(&mut self, world: &mut World) {
+        let font = {
+            let asset_registry = world.read_resource::<AssetRegistry>();
+            // Errors are still logged without the font
+            if !asset_registry.contains_font(constants::UI_FONT) {
+                return;
+            }
+            asset_registry.get_font(constants::UI_FONT)
+        };
+
         let loading_text = UiText::new(
-            font.clone(),
+            font,
             String::from("Loading..."),
             constants::UI_FONT_COLOR,
-            constants::UI_BUTTON_FONT_SIZE
+            LOADING_FONT_SIZE
         );
 
         let loading_text_transform = UiTransform::new(
@@ -207,8 +248,8 @@ impl LoadingState {
             0.0,
             0.0,
             1.0,
-            constants::UI_BUTTON_WIDTH,
-            constants::UI_BUTTON_HEIGHT,
+            LOADING_TEXT_WIDTH,
+            LOADING_TEXT_HEIGHT,
             1
         );
 
@@ -218,38 +259,111 @@ impl LoadingState {
             .with(loading_text_transform)
             .build();
         self.loading_text = Some(loading_text);
+
+        // Plain color textures, the progress bar has to be visible before any image is loaded
+        let (background_texture, bar_texture) = {
+            let loader = world.read_resource::<Loader>();
+            let texture_storage = world.read_resource::<AssetStorage<Texture>>();
+
+            (
+                loader.load_from_data(TextureData::from(PROGRESS_BAR_BACKGROUND_COLOR), (), &texture_storage),
+                loader.load_from_data(TextureData::from(constants::UI_FONT_COLOR), (), &texture_storage)
+            )
+        };
+
+        let y = -LOADING_TEXT_HEIGHT;
+        let progress_bar_background = world
+            .create_entity()
+            .with(UiImage {
+                texture: background_texture
+            })
+            .with(UiTransform::new(
+                String::from("progress_bar_background"),
+                Anchor::Middle,
+                0.0,
+                y,
+                1.0,
+                PROGRESS_BAR_WIDTH,
+                PROGRESS_BAR_HEIGHT,
+                1
+            ))
+            .build();
+        self.progress_bar_background = Some(progress_bar_background);
+
+        let progress_bar = world
+            .create_entity()
+            .with(UiImage {
+                texture: bar_texture
+            })
+            .with(UiTransform::new(
+                String::from("progress_bar"),
+                Anchor::Middle,
+                -PROGRESS_BAR_WIDTH / 2.0,
+                y,
+                2.0,
+                0.0,
+                PROGRESS_BAR_HEIGHT,
+                1
+            ))
+            .build();
+        self.progress_bar = Some(progress_bar);
     }
 
-    fn load_prefabs(&mut self, world: &mut World) {
-        let paths = fs::read_dir("assets/prefabs/enemies").unwrap();
-
-        let mut small_enemy_prefabs: Vec<EnemyPrefabData> = Vec::new();
-        let mut medium_enemy_prefabs: Vec<EnemyPrefabData> = Vec::new();
-        let mut large_enemy_prefabs: Vec<EnemyPrefabData> = Vec::new();
-        for path in paths {
-            let path = path.unwrap().path();
-            let file_name = path.file_name().unwrap().to_os_string().into_string().unwrap();//display().to_string();
-            let file_content = fs::read_to_string(path).expect("Could not read file");
-            let mut prefab: EnemyPrefabData = serde_json::from_str(file_content.as_str()).expect("Could not parse json");
-            prefab.init();
-
-            if file_name.starts_with("sm") {
-                small_enemy_prefabs.push(prefab);
-            } else if file_name.starts_with("md") {
-                medium_enemy_prefabs.push(prefab);
-            } else if file_name.starts_with("lg") {
-                large_enemy_prefabs.push(prefab);
-            }
+    // Prefab files are read on a separate thread, so the loading view keeps updating
+    fn load_prefabs(&mut self, sprite_sheets: HashMap<String, SpriteSheetManifest>) {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = sender.send(load_prefab_files(&sprite_sheets));
+        });
+        self.prefabs_receiver = Some(receiver);
+    }
+
+    fn show_load_errors(&mut self, world: &mut World, errors: Vec<String>) {
+        for error in &errors {
+            error!("{}", error);
         }
 
-        let enemy_prefabs = EnemyPrefabs {
-            small_enemy_prefabs,
-            medium_enemy_prefabs,
-            large_enemy_prefabs
+        let font = {
+            let asset_registry = world.read_resource::<AssetRegistry>();
+            if !asset_registry.contains_font(constants::UI_FONT) {
+                return;
+            }
+            asset_registry.get_font(constants::UI_FONT)
         };
+        if let Some(loading_text) = self.loading_text {
+            if let Some(text) = world.write_storage::<UiText>().get_mut(loading_text) {
+                text.text = String::from("Could not load assets");
+            }
+        }
 
-        world.register::<Handle<Prefab<EnemyPrefabData>>>();
-        world.add_resource(enemy_prefabs);
+        let screen_width = world.read_resource::<ScreenDimensions>().width();
+        let mut y = -constants::UI_ERROR_FONT_SIZE * 2.0;
+        for error in errors {
+            let error_text = UiText::new(
+                font.clone(),
+                error,
+                constants::UI_ERROR_FONT_COLOR,
+                constants::UI_ERROR_FONT_SIZE
+            );
+            let error_text_transform = UiTransform::new(
+                String::from("load_error_txt"),
+                Anchor::TopMiddle,
+                0.0,
+                y,
+                1.0,
+                screen_width - 40.0,
+                constants::UI_ERROR_FONT_SIZE,
+                1
+            );
+            let error_text = world
+                .create_entity()
+                .with(error_text)
+                .with(error_text_transform)
+                .build();
+            self.error_texts.push(error_text);
+
+            y -= constants::UI_ERROR_FONT_SIZE * 1.5;
+        }
     }
 
 }
@@ -261,17 +375,70 @@ impl SimpleState for LoadingState {
     }
 
     fn on_stop(&mut self, data: StateData<GameData>) {
-        if let Some(loading_text) = self.loading_text {
-            data.world.delete_entity(loading_text).expect("Failed to delete text");
+        let entities = self.loading_text
+            .into_iter()
+            .chain(self.progress_bar)
+            .chain(self.progress_bar_background)
+            .chain(self.error_texts.drain(..));
+        for entity in entities {
+            data.world.delete_entity(entity).expect("Failed to delete loading view");
         }
     }
 
-    fn update(&mut self, _data: &mut StateData<GameData>) -> SimpleTrans {
-        if self.load_complete {
+    fn update(&mut self, data: &mut StateData<GameData>) -> SimpleTrans {
+        // Stay on the loading screen, there is no point in starting the game with broken assets
+        if self.load_failed {
+            return Trans::None;
+        }
+
+        self.receive_prefabs(data.world);
+        self.update_progress_bar(data.world);
+        // is_complete stays false once any asset failed, errors are reported below instead
+        if self.progress_counter.num_loading() > 0 || !self.is_prefabs_loaded() {
+            return Trans::None;
+        }
+
+        let errors = self.collect_load_errors();
+        if errors.is_empty() {
             return Trans::Switch(Box::new(MainMenuState::new()));
         }
 
+        self.load_failed = true;
+        self.show_load_errors(data.world, errors);
         return Trans::None;
     }
 
-}
\ No newline at end of file
+}
+
+fn load_prefab_files(sprite_sheets: &HashMap<String, SpriteSheetManifest>) -> LoadedPrefabs {
+    let mut errors: Vec<PrefabError> = Vec::new();
+    let mut sprite_names = SpriteNames::default();
+    for (sheet, sprite_sheet) in sprite_sheets {
+        if let Some(names_path) = &sprite_sheet.names {
+            match load_sprite_name_map(names_path, &sprite_sheet.definition) {
+                Ok(name_map) => sprite_names.insert(sheet, name_map),
+                Err(err) => errors.push(PrefabError::new(names_path, err))
+            }
+        }
+    }
+
+    // Sprites used directly by the code instead of a prefab
+    for (sheet, name) in &constants::REQUIRED_SPRITES {
+        let sprite = SpriteRef::new(sheet, name);
+        if !sprite_names.contains(&sprite) {
+            errors.push(PrefabError::new(ASSET_MANIFEST_PATH, format!("sprite \"{}\" is required by the game", sprite)));
+        }
+    }
+
+    let enemy_prefabs = prefabs::load_enemy_prefabs(&sprite_names, &mut errors);
+    let player_ship_prefabs = prefabs::load_player_ship_prefabs(&sprite_names, &mut errors);
+    // Levels refer to enemy prefabs, so they are loaded last
+    let campaign = prefabs::load_campaign(&sprite_names, &enemy_prefabs, &mut errors);
+    return LoadedPrefabs {
+        sprite_names,
+        enemy_prefabs,
+        player_ship_prefabs,
+        campaign,
+        errors
+    };
+}
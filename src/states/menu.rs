@@ -0,0 +1,237 @@
+use amethyst::{
+    ecs::Entity,
+    input,
+    prelude::*,
+    renderer::VirtualKeyCode,
+    ui::{
+        Anchor,
+        UiButtonBuilder,
+        UiEventType,
+        UiImage,
+        UiText,
+        UiTransform
+    }
+};
+use crate::resources::{
+    AssetRegistry,
+    Console,
+    MenuLayout,
+    MenuLayouts,
+    TextLayout
+};
+
+const DEFAULT_LAYOUT: &str = "default";
+
+// Declarative button menu, states describe the title, text lines and the labelled actions and get
+// the typed action back from handle_event. Positions and styles come from the menu layouts.
+pub struct Menu<A: Copy> {
+    layout: String,
+    title: Option<String>,
+    lines: Vec<String>,
+    items: Vec<(String, A)>,
+    layer: f32,
+    focused: usize,
+    ui_elements: Vec<Entity>,
+    buttons: Vec<(Entity, A)>
+}
+
+impl<A: Copy> Menu<A> {
+
+    pub fn new() -> Self {
+        return Self {
+            layout: String::from(DEFAULT_LAYOUT),
+            title: None,
+            lines: Vec::new(),
+            items: Vec::new(),
+            layer: 1.0,
+            focused: 0,
+            ui_elements: Vec::new(),
+            buttons: Vec::new()
+        };
+    }
+
+    pub fn with_layout(mut self, layout: &str) -> Self {
+        self.layout = String::from(layout);
+        return self;
+    }
+
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = Some(String::from(title));
+        return self;
+    }
+
+    pub fn with_action(mut self, label: &str, action: A) -> Self {
+        self.items.push((String::from(label), action));
+        return self;
+    }
+
+    // Menus shown on top of the gameplay need a higher layer than the gameplay UI
+    pub fn with_layer(mut self, layer: f32) -> Self {
+        self.layer = layer;
+        return self;
+    }
+
+    // Setters are shown after the menu is built again
+    pub fn set_title(&mut self, title: &str) {
+        self.title = Some(String::from(title));
+    }
+
+    pub fn set_label(&mut self, index: usize, label: &str) {
+        if let Some(item) = self.items.get_mut(index) {
+            item.0 = String::from(label);
+        }
+    }
+
+    pub fn set_lines(&mut self, lines: Vec<String>) {
+        self.lines = lines;
+    }
+
+    pub fn get_layout(&self, world: &World) -> MenuLayout {
+        return world
+            .read_resource::<MenuLayouts>()
+            .get(&self.layout)
+            .cloned()
+            .unwrap_or_else(|| panic!("Menu layout \"{}\" should have been validated while loading!", self.layout));
+    }
+
+    pub fn build(&mut self, world: &mut World) {
+        let layout = self.get_layout(world);
+
+        if let Some(title) = self.title.clone() {
+            let title_text = create_text(world, title, &layout.title.text, UiTransform::new(
+                String::from("menu_title_txt"),
+                Anchor::TopMiddle,
+                0.0,
+                layout.title.y,
+                self.layer,
+                layout.title.width,
+                layout.title.height,
+                1
+            ));
+            self.ui_elements.push(title_text);
+        }
+
+        for (i, line) in self.lines.clone().into_iter().enumerate() {
+            let line_text = create_text(world, line, &layout.lines.text, UiTransform::new(
+                format!("menu_line_txt_{}", i),
+                Anchor::TopMiddle,
+                0.0,
+                layout.lines.y - layout.lines.line_height * i as f32,
+                self.layer,
+                layout.lines.width,
+                layout.lines.line_height,
+                1
+            ));
+            self.ui_elements.push(line_text);
+        }
+
+        let (font, button_image, button_hover_image) = {
+            let asset_registry = world.read_resource::<AssetRegistry>();
+
+            (
+                asset_registry.get_font(&layout.buttons.text.font),
+                asset_registry.get_texture(&layout.buttons.image),
+                asset_registry.get_texture(&layout.buttons.hover_image)
+            )
+        };
+        let row_height = layout.buttons.height + layout.buttons.spacing;
+        for (i, (label, action)) in self.items.iter().enumerate() {
+            let (x, y, width, height) = match layout.buttons.placements.get(i) {
+                Some(placement) => (placement.x, placement.y, placement.width, placement.height),
+                None => (0.0, layout.buttons.y - row_height * i as f32, layout.buttons.width, layout.buttons.height)
+            };
+            let button = UiButtonBuilder::new(format!("menu_btn_{}", i), label.clone())
+                .with_position(x, y)
+                .with_layer(self.layer)
+                .with_size(width, height)
+                .with_anchor(layout.buttons.anchor)
+                .with_font(font.clone())
+                .with_text_color(layout.buttons.text.color)
+                .with_font_size(layout.buttons.text.font_size)
+                .with_image(button_image.clone())
+                .with_hover_image(button_hover_image.clone())
+                .build_from_world(world);
+            self.ui_elements.push(button);
+            self.buttons.push((button, *action));
+        }
+
+        // Rebuilt menus keep their focus
+        self.focused = self.focused.min(self.buttons.len().saturating_sub(1));
+        self.show_focus(world);
+    }
+
+    pub fn clear(&mut self, world: &mut World) {
+        self.buttons.clear();
+        self.ui_elements.drain(..).for_each(|element| {
+            world
+                .delete_entity(element)
+                .expect("Failed to delete menu element");
+        });
+    }
+
+    // Returns the action of a clicked button or of the focused button when Return is pressed,
+    // arrow keys and hovering move the focus
+    pub fn handle_event(&mut self, world: &mut World, event: &StateEvent) -> Option<A> {
+        // Keys typed into the console are not meant for the menu
+        if self.buttons.is_empty() || world.read_resource::<Console>().is_open {
+            return None;
+        }
+
+        if let StateEvent::Ui(ui_event) = event {
+            let index = self.buttons
+                .iter()
+                .position(|(button, _)| *button == ui_event.target)?;
+            match ui_event.event_type {
+                UiEventType::Click => return Some(self.buttons[index].1),
+                UiEventType::HoverStart => {
+                    self.focused = index;
+                    self.show_focus(world);
+                },
+                _ => {}
+            }
+        } else if let StateEvent::Window(event) = event {
+            if input::is_key_down(&event, VirtualKeyCode::Return) {
+                return Some(self.buttons[self.focused].1);
+            } else if input::is_key_down(&event, VirtualKeyCode::Up) {
+                self.focused = (self.focused + self.buttons.len() - 1) % self.buttons.len();
+                self.show_focus(world);
+            } else if input::is_key_down(&event, VirtualKeyCode::Down) {
+                self.focused = (self.focused + 1) % self.buttons.len();
+                self.show_focus(world);
+            }
+        }
+
+        return None;
+    }
+
+    fn show_focus(&self, world: &mut World) {
+        let layout = self.get_layout(world);
+        let (button_image, button_hover_image) = {
+            let asset_registry = world.read_resource::<AssetRegistry>();
+
+            (asset_registry.get_texture(&layout.buttons.image), asset_registry.get_texture(&layout.buttons.hover_image))
+        };
+
+        let mut images = world.write_storage::<UiImage>();
+        for (i, (button, _)) in self.buttons.iter().enumerate() {
+            if let Some(image) = images.get_mut(*button) {
+                image.texture = if i == self.focused {
+                    button_hover_image.clone()
+                } else {
+                    button_image.clone()
+                };
+            }
+        }
+    }
+
+}
+
+// Text element styled by a menu layout, for screens which show more than the menu itself
+pub fn create_text(world: &mut World, text: String, text_layout: &TextLayout, transform: UiTransform) -> Entity {
+    let font = world.read_resource::<AssetRegistry>().get_font(&text_layout.font);
+    return world
+        .create_entity()
+        .with(UiText::new(font, text, text_layout.color, text_layout.font_size))
+        .with(transform)
+        .build();
+}
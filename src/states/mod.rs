@@ -1,13 +1,25 @@
+mod controls;
+mod level_complete;
+mod level_select;
 mod load;
 mod main_menu;
+mod menu;
 mod gameplay;
+mod options;
 mod pause;
 mod result;
+mod ship_select;
 
 pub use {
+    controls::ControlsState,
     gameplay::GameplayState,
+    level_complete::LevelCompleteState,
+    level_select::LevelSelectState,
     load::LoadingState,
     main_menu::MainMenuState,
+    menu::{Menu, create_text},
+    options::OptionsState,
     pause::PauseState,
-    result::ResultState
+    result::ResultState,
+    ship_select::ShipSelectState
 };
\ No newline at end of file
@@ -0,0 +1,83 @@
+use amethyst::{
+    prelude::*,
+    renderer::VirtualKeyCode,
+    input
+};
+use crate::{
+    resources::GameOptions,
+    states::Menu
+};
+
+#[derive(Clone, Copy)]
+enum OptionsAction {
+    Fullscreen,
+    PauseOnFocusLost,
+    Back
+}
+
+pub struct OptionsState {
+    menu: Menu<OptionsAction>
+}
+
+impl OptionsState {
+
+    pub fn new() -> Self {
+        return Self {
+            menu: Menu::new()
+                .with_title("Options")
+                .with_layer(10.0)
+                .with_action("", OptionsAction::Fullscreen)
+                .with_action("", OptionsAction::PauseOnFocusLost)
+                .with_action("Back", OptionsAction::Back)
+        };
+    }
+
+    // Labels show the current value of each option
+    fn build_menu(&mut self, world: &mut World) {
+        let (is_fullscreen, is_pause_on_focus_lost) = {
+            let game_options = world.read_resource::<GameOptions>();
+            (game_options.is_fullscreen, game_options.is_pause_on_focus_lost)
+        };
+        self.menu.set_label(0, &format!("Fullscreen: {}", if is_fullscreen { "On" } else { "Off" }));
+        self.menu.set_label(1, &format!("Auto Pause: {}", if is_pause_on_focus_lost { "On" } else { "Off" }));
+        self.menu.build(world);
+    }
+
+}
+
+impl SimpleState for OptionsState {
+
+    fn handle_event(&mut self, data: StateData<GameData>, event: StateEvent) -> SimpleTrans {
+        if let StateEvent::Window(event) = &event {
+            if input::is_key_down(&event, VirtualKeyCode::Escape) {
+                return Trans::Pop;
+            }
+        }
+
+        match self.menu.handle_event(data.world, &event) {
+            Some(OptionsAction::Fullscreen) => {
+                let mut game_options = data.world.write_resource::<GameOptions>();
+                game_options.is_fullscreen = !game_options.is_fullscreen;
+            },
+            Some(OptionsAction::PauseOnFocusLost) => {
+                let mut game_options = data.world.write_resource::<GameOptions>();
+                game_options.is_pause_on_focus_lost = !game_options.is_pause_on_focus_lost;
+            },
+            Some(OptionsAction::Back) => return Trans::Pop,
+            None => return Trans::None
+        }
+
+        self.menu.clear(data.world);
+        self.build_menu(data.world);
+        return Trans::None;
+    }
+
+    fn on_start(&mut self, data: StateData<GameData>) {
+        self.build_menu(data.world);
+    }
+
+    fn on_stop(&mut self, data: StateData<GameData>) {
+        self.menu.clear(data.world);
+    }
+
+}
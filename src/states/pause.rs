@@ -1,72 +1,158 @@
 use amethyst::{
-    ecs::Entity,
+    assets::{
+        AssetStorage,
+        Loader
+    },
+    ecs::{
+        Entity,
+        Join
+    },
     prelude::*,
-    renderer::VirtualKeyCode,
+    renderer::{
+        Texture,
+        TextureData,
+        VirtualKeyCode
+    },
     input,
+    shrev::EventChannel,
     ui::{
-        UiTransform,
-        UiButtonBuilder,
-        UiEventType,
-        Anchor
-    },
-    shrev::EventChannel
+        Anchor,
+        Stretch,
+        UiImage,
+        UiTransform
+    }
 };
 use crate::{
-    resources::UiAssets,
-    constants
+    components::{
+        Player,
+        Regeneration,
+        Shield,
+        tags::InvulnerableTag
+    },
+    constants,
+    resources::{
+        GameplaySessionData,
+        PauseOverlayTexture
+    },
+    states::{
+        ControlsState,
+        GameplayState,
+        Menu,
+        OptionsState
+    }
 };
 
+#[derive(Clone, Copy)]
+enum PauseAction {
+    Resume,
+    Restart,
+    Options,
+    Controls,
+    MainMenu
+}
+
 pub struct PauseState {
-    buttons: Vec<Entity>
+    menu: Menu<PauseAction>,
+    player_ships: Vec<String>,
+    level: Option<usize>,
+    overlay: Option<Entity>
 }
 
 impl PauseState {
 
-    pub fn new() -> Self {
+    pub fn new(player_ships: Vec<String>, level: Option<usize>) -> Self {
+        // Campaign levels are started from the level select
+        let main_menu_label = if level.is_some() { "Level Select" } else { "Main Menu" };
         return Self {
-            buttons: Vec::new()
+            menu: Menu::new()
+                .with_title("Paused")
+                .with_layer(10.0)
+                .with_action("Resume", PauseAction::Resume)
+                .with_action("Restart", PauseAction::Restart)
+                .with_action("Options", PauseAction::Options)
+                .with_action("Controls", PauseAction::Controls)
+                .with_action(main_menu_label, PauseAction::MainMenu),
+            player_ships,
+            level,
+            overlay: None
         };
     }
 
-    fn create_menu(&mut self, world: &mut World) {
-        let ui_assets = world.read_resource::<UiAssets>();
-
-        let x = 0.0;
-        let y = constants::UI_BUTTON_HEIGHT + constants::UI_BUTTON_HEIGHT / 4.0;
-
-        let resume_button = UiButtonBuilder::new("resume_btn", "Resume")
-            .with_position(x, y)
-            .with_layer(10.0)
-            .with_size(constants::UI_BUTTON_WIDTH, constants::UI_BUTTON_HEIGHT)
-            .with_anchor(Anchor::Middle)
-            .with_font(ui_assets.get_font())
-            .with_text_color(constants::UI_FONT_COLOR)
-            .with_font_size(constants::UI_BUTTON_FONT_SIZE)
-            .with_image(ui_assets.get_btn_img())
-            .with_hover_image(ui_assets.get_btn_hover_img())
-            .build_from_world(world);
-        self.buttons.push(resume_button);
-
-        let main_menu_button = UiButtonBuilder::new("main_menu_btn", "Main Menu")
-            .with_position(x, y - constants::UI_BUTTON_HEIGHT - constants::UI_BUTTON_HEIGHT / 4.0)
-            .with_layer(10.0)
-            .with_size(constants::UI_BUTTON_WIDTH, constants::UI_BUTTON_HEIGHT)
-            .with_anchor(Anchor::Middle)
-            .with_font(ui_assets.get_font())
-            .with_text_color(constants::UI_FONT_COLOR)
-            .with_font_size(constants::UI_BUTTON_FONT_SIZE)
-            .with_image(ui_assets.get_btn_img())
-            .with_hover_image(ui_assets.get_btn_hover_img())
-            .build_from_world(world);
-        self.buttons.push(main_menu_button);
+    // Dims the gameplay under the pause screens
+    fn create_overlay(world: &mut World) -> Entity {
+        if !world.res.has_value::<PauseOverlayTexture>() {
+            let texture = world.read_resource::<Loader>().load_from_data(
+                TextureData::from(constants::PAUSE_OVERLAY_COLOR),
+                (),
+                &world.read_resource::<AssetStorage<Texture>>()
+            );
+            world.add_resource(PauseOverlayTexture { texture });
+        }
+        let texture = world.read_resource::<PauseOverlayTexture>().texture.clone();
+        let mut transform = UiTransform::new(
+            String::from("pause_overlay"),
+            Anchor::Middle,
+            0.0,
+            0.0,
+            9.0,
+            0.0,
+            0.0,
+            1
+        );
+        transform.stretch = Some(Stretch::XY { x_margin: 0.0, y_margin: 0.0 });
+
+        return world
+            .create_entity()
+            .with(UiImage { texture })
+            .with(transform)
+            .build();
     }
 
-    fn clear_menu(&mut self, world: &mut World) {
-        self.buttons.drain(..).for_each(|button| {
-            world
-                .delete_entity(button)
-                .expect("Failed to delete button");
-        });
+    fn get_summary_lines(world: &World) -> Vec<String> {
+        let session_data = world.read_resource::<GameplaySessionData>();
+        let elapsed_seconds = session_data.get_elapsed_time() as u32;
+        let mut lines = vec![format!("Time {:02}:{:02}", elapsed_seconds / 60, elapsed_seconds % 60)];
+
+        let players = world.read_storage::<Player>();
+        let shields = world.read_storage::<Shield>();
+        let regenerations = world.read_storage::<Regeneration>();
+        let invulnerable_tags = world.read_storage::<InvulnerableTag>();
+        let mut player_lines: Vec<(usize, String)> = (&world.entities(), &players)
+            .join()
+            .map(|(entity, player)| {
+                let mut power_ups = Vec::new();
+                if let Some(shield) = shields.get(entity).filter(|shield| shield.get_charges() > 0) {
+                    power_ups.push(format!("Shield x{}", shield.get_charges()));
+                }
+                if regenerations.contains(entity) {
+                    power_ups.push(String::from("Regeneration"));
+                }
+                if invulnerable_tags.contains(entity) {
+                    power_ups.push(String::from("Invulnerable"));
+                }
+                if power_ups.is_empty() {
+                    power_ups.push(String::from("none"));
+                }
+
+                let player_index = player.get_index();
+                return (player_index, format!(
+                    "Player {}  Score {}  Power-ups: {}",
+                    player_index + 1,
+                    session_data.get_score(player_index),
+                    power_ups.join(", ")
+                ));
+            })
+            .collect();
+        player_lines.sort_by_key(|(player_index, _)| *player_index);
+        lines.extend(player_lines.into_iter().map(|(_, line)| line));
+
+        return lines;
+    }
+
+    // Summary is taken again every time the menu is shown
+    fn build_menu(&mut self, world: &mut World) {
+        self.menu.set_lines(PauseState::get_summary_lines(world));
+        self.menu.build(world);
     }
 
 }
@@ -80,38 +166,49 @@ impl SimpleState for PauseState {
             }
         }
 
-        return match &event {
-            StateEvent::Ui(ui_event) => {
-                match ui_event.event_type {
-                    UiEventType::Click => {
-                        if let Some(ui_transform) = data.world.read_storage::<UiTransform>().get(ui_event.target) {
-                            match ui_transform.id.as_ref() {
-                                "resume_btn" => Trans::Pop,
-                                "main_menu_btn" => {
-                                    data.world
-                                        .write_resource::<EventChannel<TransEvent<GameData, StateEvent>>>()
-                                        .single_write(Box::new(|| Trans::Pop));
-                                    Trans::Pop
-                                },
-                                _ => Trans::None
-                            }
-                        } else {
-                            Trans::None
-                        }
-                    },
-                    _ => Trans::None
-                }
+        return match self.menu.handle_event(data.world, &event) {
+            Some(PauseAction::Resume) => Trans::Pop,
+            Some(PauseAction::Restart) => {
+                let (player_ships, level) = (self.player_ships.clone(), self.level);
+                let mut event_channel = data.world.write_resource::<EventChannel<TransEvent<GameData, StateEvent>>>();
+                event_channel.single_write(Box::new(|| Trans::Pop));
+                event_channel.single_write(Box::new(|| Trans::Pop));
+                event_channel.single_write(Box::new(move || Trans::Push(Box::new(GameplayState::new(player_ships, level)))));
+                Trans::None
             },
-            _ => Trans::None
+            Some(PauseAction::Options) => Trans::Push(Box::new(OptionsState::new())),
+            Some(PauseAction::Controls) => Trans::Push(Box::new(ControlsState::new())),
+            Some(PauseAction::MainMenu) => {
+                data.world
+                    .write_resource::<EventChannel<TransEvent<GameData, StateEvent>>>()
+                    .single_write(Box::new(|| Trans::Pop));
+                Trans::Pop
+            },
+            None => Trans::None
         }
     }
 
     fn on_start(&mut self, data: StateData<GameData>) {
-        self.create_menu(data.world);
+        self.overlay = Some(PauseState::create_overlay(data.world));
+        self.build_menu(data.world);
     }
 
     fn on_stop(&mut self, data: StateData<GameData>) {
-        self.clear_menu(data.world);
+        self.menu.clear(data.world);
+        if let Some(overlay) = self.overlay.take() {
+            data.world
+                .delete_entity(overlay)
+                .expect("Failed to delete pause overlay");
+        }
+    }
+
+    // Options and controls are shown over the overlay instead of the pause menu
+    fn on_pause(&mut self, data: StateData<GameData>) {
+        self.menu.clear(data.world);
     }
 
-}
\ No newline at end of file
+    fn on_resume(&mut self, data: StateData<GameData>) {
+        self.build_menu(data.world);
+    }
+
+}
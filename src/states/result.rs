@@ -1,75 +1,40 @@
 use amethyst::{
-    ecs::Entity,
     prelude::*,
     renderer::VirtualKeyCode,
     input,
-    ui::{
-        UiTransform,
-        UiButtonBuilder,
-        UiEventType,
-        Anchor
-    },
     shrev::EventChannel
 };
-use crate::{
-    resources::UiAssets,
-    constants,
-    states::GameplayState
+use crate::states::{
+    GameplayState,
+    Menu
 };
 
+#[derive(Clone, Copy)]
+enum ResultAction {
+    Restart,
+    MainMenu
+}
+
 pub struct ResultState {
-    buttons: Vec<Entity>
+    menu: Menu<ResultAction>,
+    player_ships: Vec<String>,
+    level: Option<usize>
 }
 
 impl ResultState {
 
-    pub fn new() -> Self {
+    pub fn new(player_ships: Vec<String>, level: Option<usize>) -> Self {
+        let main_menu_label = if level.is_some() { "Level Select" } else { "Main Menu" };
         return Self {
-            buttons: Vec::new()
+            menu: Menu::new()
+                .with_layer(10.0)
+                .with_action("Restart", ResultAction::Restart)
+                .with_action(main_menu_label, ResultAction::MainMenu),
+            player_ships,
+            level
         };
     }
 
-    fn create_menu(&mut self, world: &mut World) {
-        let ui_assets = world.read_resource::<UiAssets>();
-
-        let x = 0.0;
-        let y = constants::UI_BUTTON_HEIGHT + constants::UI_BUTTON_HEIGHT / 4.0;
-
-        let resume_button = UiButtonBuilder::new("restart_btn", "Restart")
-            .with_position(x, y)
-            .with_layer(10.0)
-            .with_size(constants::UI_BUTTON_WIDTH, constants::UI_BUTTON_HEIGHT)
-            .with_anchor(Anchor::Middle)
-            .with_font(ui_assets.get_font())
-            .with_text_color(constants::UI_FONT_COLOR)
-            .with_font_size(constants::UI_BUTTON_FONT_SIZE)
-            .with_image(ui_assets.get_btn_img())
-            .with_hover_image(ui_assets.get_btn_hover_img())
-            .build_from_world(world);
-        self.buttons.push(resume_button);
-
-        let main_menu_button = UiButtonBuilder::new("main_menu_btn", "Main Menu")
-            .with_position(x, y - constants::UI_BUTTON_HEIGHT - constants::UI_BUTTON_HEIGHT / 4.0)
-            .with_layer(10.0)
-            .with_size(constants::UI_BUTTON_WIDTH, constants::UI_BUTTON_HEIGHT)
-            .with_anchor(Anchor::Middle)
-            .with_font(ui_assets.get_font())
-            .with_text_color(constants::UI_FONT_COLOR)
-            .with_font_size(constants::UI_BUTTON_FONT_SIZE)
-            .with_image(ui_assets.get_btn_img())
-            .with_hover_image(ui_assets.get_btn_hover_img())
-            .build_from_world(world);
-        self.buttons.push(main_menu_button);
-    }
-
-    fn clear_menu(&mut self, world: &mut World) {
-        self.buttons.drain(..).for_each(|button| {
-            world
-                .delete_entity(button)
-                .expect("Failed to delete button");
-        });
-    }
-
 }
 
 impl SimpleState for ResultState {
@@ -81,46 +46,31 @@ impl SimpleState for ResultState {
             }
         }
 
-        return match &event {
-            StateEvent::Ui(ui_event) => {
-                match ui_event.event_type {
-                    UiEventType::Click => {
-                        if let Some(ui_transform) = data.world.read_storage::<UiTransform>().get(ui_event.target) {
-                            match ui_transform.id.as_ref() {
-                                "restart_btn" => {
-                                    let mut event_channel = data.world.write_resource::<EventChannel<TransEvent<GameData, StateEvent>>>();
-                                    event_channel.single_write(Box::new(|| Trans::Pop));
-                                    event_channel.single_write(Box::new(|| Trans::Pop));
-                                    event_channel.single_write(Box::new(|| Trans::Push(Box::new(GameplayState::new()))));
-
-                                    Trans::None
-                                },
-                                "main_menu_btn" => {
-                                    let mut event_channel = data.world.write_resource::<EventChannel<TransEvent<GameData, StateEvent>>>();
-                                    event_channel.single_write(Box::new(|| Trans::Pop));
-                                    event_channel.single_write(Box::new(|| Trans::Pop));
-                                    
-                                    Trans::None
-                                },
-                                _ => Trans::None
-                            }
-                        } else {
-                            Trans::None
-                        }
-                    },
-                    _ => Trans::None
-                }
+        match self.menu.handle_event(data.world, &event) {
+            Some(ResultAction::Restart) => {
+                let (player_ships, level) = (self.player_ships.clone(), self.level);
+                let mut event_channel = data.world.write_resource::<EventChannel<TransEvent<GameData, StateEvent>>>();
+                event_channel.single_write(Box::new(|| Trans::Pop));
+                event_channel.single_write(Box::new(|| Trans::Pop));
+                event_channel.single_write(Box::new(move || Trans::Push(Box::new(GameplayState::new(player_ships, level)))));
             },
-            _ => Trans::None
+            Some(ResultAction::MainMenu) => {
+                let mut event_channel = data.world.write_resource::<EventChannel<TransEvent<GameData, StateEvent>>>();
+                event_channel.single_write(Box::new(|| Trans::Pop));
+                event_channel.single_write(Box::new(|| Trans::Pop));
+            },
+            None => {}
         }
+
+        return Trans::None;
     }
 
     fn on_start(&mut self, data: StateData<GameData>) {
-        self.create_menu(data.world);
+        self.menu.build(data.world);
     }
 
     fn on_stop(&mut self, data: StateData<GameData>) {
-        self.clear_menu(data.world);
+        self.menu.clear(data.world);
     }
 
-}
\ No newline at end of file
+}
@@ -0,0 +1,374 @@
+use amethyst::{
+    core::Transform,
+    ecs::Entity,
+    input,
+    prelude::*,
+    renderer::{
+        Camera,
+        Projection,
+        VirtualKeyCode
+    },
+    ui::{
+        Anchor,
+        UiImage,
+        UiTransform
+    }
+};
+use crate::{
+    constants,
+    prefabs::PlayerShipPrefabs,
+    resources::{
+        AssetRegistry,
+        GameRules,
+        SpriteRef,
+        SpriteSheets
+    },
+    states::{
+        GameplayState,
+        Menu,
+        create_text
+    }
+};
+
+const PREVIEW_SCALE: f32 = 1.5;
+
+// Select comes first so Return picks the shown ship, arrow buttons are placed by the layout
+#[derive(Clone, Copy)]
+enum ShipSelectAction {
+    Select,
+    Back,
+    Previous,
+    Next
+}
+
+// Everything the selection screen shows about a single ship
+struct ShipInfo {
+    name: String,
+    sprite: SpriteRef,
+    // Stats relative to the best ship, from 0.0 to 1.0
+    stats: Vec<(&'static str, f32)>,
+    abilities: Vec<String>
+}
+
+pub struct ShipSelectState {
+    menu: Menu<ShipSelectAction>,
+    player_count: usize,
+    ships: Vec<ShipInfo>,
+    selected_ships: Vec<String>,
+    current_ship: usize,
+    camera: Option<Entity>,
+    ship_elements: Vec<Entity>,
+    // Campaign level to play, endless mode when not set
+    level: Option<usize>
+}
+
+impl ShipSelectState {
+
+    pub fn new(player_count: usize) -> Self {
+        return Self {
+            menu: Menu::new()
+                .with_layout("ship_select")
+                .with_action("Select", ShipSelectAction::Select)
+                .with_action("Back", ShipSelectAction::Back)
+                .with_action("<", ShipSelectAction::Previous)
+                .with_action(">", ShipSelectAction::Next),
+            player_count,
+            ships: Vec::new(),
+            selected_ships: Vec::new(),
+            current_ship: 0,
+            camera: None,
+            ship_elements: Vec::new(),
+            level: None
+        };
+    }
+
+    pub fn with_level(mut self, level: usize) -> Self {
+        self.level = Some(level);
+        return self;
+    }
+
+    fn load_ships(&mut self, world: &mut World) {
+        let player_ship_prefabs = world.read_resource::<PlayerShipPrefabs>();
+        let prefabs: Vec<_> = player_ship_prefabs
+            .names()
+            .iter()
+            .filter_map(|name| player_ship_prefabs.get(name))
+            .collect();
+
+        let max_speed = prefabs.iter().map(|prefab| prefab.movement_speed).fold(0.0, f32::max);
+        let max_health = prefabs.iter().map(|prefab| prefab.max_health).max().unwrap_or(1) as f32;
+        let max_fire_rate = prefabs.iter().map(|prefab| prefab.get_fire_rate()).fold(0.0, f32::max);
+
+        self.ships = prefabs
+            .iter()
+            .map(|prefab| ShipInfo {
+                name: prefab.name.clone(),
+                sprite: prefab.sprite.clone(),
+                stats: vec![
+                    ("Speed", prefab.movement_speed / max_speed),
+                    ("Health", prefab.max_health as f32 / max_health),
+                    ("Firepower", prefab.get_fire_rate() / max_fire_rate)
+                ],
+                abilities: prefab.abilities
+                    .iter()
+                    .map(|ability| ability.get_description())
+                    .collect()
+            })
+            .collect();
+        self.current_ship = self.ships
+            .iter()
+            .position(|ship| ship.name == constants::DEFAULT_PLAYER_SHIP)
+            .unwrap_or(0);
+    }
+
+    // Shows the ship preview sprite
+    fn create_camera(world: &mut World) -> Entity {
+        let (arena_width, arena_height) = {
+            let game_rules = world.read_resource::<GameRules>();
+            (game_rules.arena_width, game_rules.arena_height)
+        };
+        let mut camera_transform = Transform::default();
+        camera_transform.set_z(1.0);
+        return world
+            .create_entity()
+            .with(Camera::from(Projection::orthographic(
+                0.0,
+                arena_width,
+                0.0,
+                arena_height
+            )))
+            .with(camera_transform)
+            .build();
+    }
+
+    // Rebuilds everything that depends on the current player and ship
+    fn refresh_ship(&mut self, world: &mut World) {
+        self.clear_ship(world);
+        self.menu.clear(world);
+
+        let title = if self.player_count > 1 {
+            format!("Player {}: choose your ship", self.selected_ships.len() + 1)
+        } else {
+            String::from("Choose your ship")
+        };
+        self.menu.set_title(&title);
+        self.menu.build(world);
+
+        let ship = match self.ships.get(self.current_ship) {
+            Some(ship) => ship,
+            None => return
+        };
+        let layout = self.menu.get_layout(world);
+        let (arena_width, arena_height) = {
+            let game_rules = world.read_resource::<GameRules>();
+            (game_rules.arena_width, game_rules.arena_height)
+        };
+        let (stat_bar_border_image, stat_bar_fill_image) = {
+            let asset_registry = world.read_resource::<AssetRegistry>();
+
+            (
+                asset_registry.get_texture(constants::STAT_BAR_BORDER_TEXTURE),
+                asset_registry.get_texture(constants::STAT_BAR_FILL_TEXTURE)
+            )
+        };
+
+        let preview = world
+            .read_resource::<SpriteSheets>()
+            .get_sprite_render(&ship.sprite)
+            .expect("Sprite should have been validated when loading the prefab!");
+        let mut preview_transform = Transform::default();
+        preview_transform.set_xyz(arena_width / 2.0, arena_height - 200.0, 0.0);
+        preview_transform.set_scale(PREVIEW_SCALE, PREVIEW_SCALE, 1.0);
+        let preview = world
+            .create_entity()
+            .with(preview)
+            .with(preview_transform)
+            .build();
+        self.ship_elements.push(preview);
+
+        // Details start at the lines of the layout, the name uses the button text style
+        let mut y = layout.lines.y;
+        let name_text = create_text(world, ship.name.clone(), &layout.buttons.text, UiTransform::new(
+            String::from("ship_name_txt"),
+            Anchor::TopMiddle,
+            0.0,
+            y,
+            1.0,
+            layout.lines.width,
+            layout.buttons.height,
+            1
+        ));
+        self.ship_elements.push(name_text);
+        y -= layout.buttons.height;
+
+        for (stat_name, ratio) in &ship.stats {
+            let stat_text = create_text(world, String::from(*stat_name), &layout.lines.text, UiTransform::new(
+                format!("{}_stat_txt", stat_name.to_lowercase()),
+                Anchor::TopMiddle,
+                -constants::UI_STAT_BAR_WIDTH / 2.0 - 60.0,
+                y,
+                1.0,
+                100.0,
+                constants::UI_STAT_BAR_HEIGHT,
+                1
+            ));
+            self.ship_elements.push(stat_text);
+
+            let stat_bar_border = world
+                .create_entity()
+                .with(UiImage {
+                    texture: stat_bar_border_image.clone()
+                })
+                .with(UiTransform::new(
+                    format!("{}_stat_bar_border", stat_name.to_lowercase()),
+                    Anchor::TopMiddle,
+                    constants::UI_STAT_BAR_WIDTH / 4.0,
+                    y,
+                    1.0,
+                    constants::UI_STAT_BAR_WIDTH,
+                    constants::UI_STAT_BAR_HEIGHT,
+                    1
+                ))
+                .build();
+            self.ship_elements.push(stat_bar_border);
+
+            // Fill is aligned to the left edge of the border
+            let fill_width = constants::UI_STAT_BAR_WIDTH * ratio.max(0.0).min(1.0);
+            let stat_bar_fill = world
+                .create_entity()
+                .with(UiImage {
+                    texture: stat_bar_fill_image.clone()
+                })
+                .with(UiTransform::new(
+                    format!("{}_stat_bar_fill", stat_name.to_lowercase()),
+                    Anchor::TopMiddle,
+                    constants::UI_STAT_BAR_WIDTH / 4.0 - (constants::UI_STAT_BAR_WIDTH - fill_width) / 2.0,
+                    y,
+                    2.0,
+                    fill_width,
+                    constants::UI_STAT_BAR_HEIGHT,
+                    1
+                ))
+                .build();
+            self.ship_elements.push(stat_bar_fill);
+
+            y -= constants::UI_STAT_BAR_HEIGHT * 2.0;
+        }
+
+        let abilities = if ship.abilities.is_empty() {
+            vec![String::from("No special abilities")]
+        } else {
+            ship.abilities.clone()
+        };
+        for ability in abilities {
+            let ability_text = create_text(world, ability, &layout.lines.text, UiTransform::new(
+                String::from("ship_ability_txt"),
+                Anchor::TopMiddle,
+                0.0,
+                y,
+                1.0,
+                layout.lines.width,
+                layout.lines.line_height,
+                1
+            ));
+            self.ship_elements.push(ability_text);
+
+            y -= layout.lines.line_height;
+        }
+    }
+
+    fn clear_ship(&mut self, world: &mut World) {
+        self.ship_elements.drain(..).for_each(|element| {
+            world
+                .delete_entity(element)
+                .expect("Failed to delete ship element");
+        });
+    }
+
+    fn clear_menu(&mut self, world: &mut World) {
+        self.clear_ship(world);
+        self.menu.clear(world);
+        if let Some(camera) = self.camera.take() {
+            world
+                .delete_entity(camera)
+                .expect("Failed to delete ship select camera");
+        }
+    }
+
+    fn change_ship(&mut self, world: &mut World, offset: isize) {
+        if self.ships.is_empty() {
+            return;
+        }
+        let ship_count = self.ships.len() as isize;
+        self.current_ship = ((self.current_ship as isize + offset + ship_count) % ship_count) as usize;
+        self.refresh_ship(world);
+    }
+
+    fn select_ship(&mut self, world: &mut World) -> SimpleTrans {
+        let ship_name = match self.ships.get(self.current_ship) {
+            Some(ship) => ship.name.clone(),
+            None => return Trans::None
+        };
+        self.selected_ships.push(ship_name);
+
+        if self.selected_ships.len() >= self.player_count {
+            return Trans::Switch(Box::new(GameplayState::new(self.selected_ships.clone(), self.level)));
+        }
+
+        self.refresh_ship(world);
+        return Trans::None;
+    }
+
+    fn go_back(&mut self, world: &mut World) -> SimpleTrans {
+        // Second player goes back to the first player's choice
+        if self.selected_ships.pop().is_some() {
+            self.refresh_ship(world);
+            return Trans::None;
+        }
+
+        return Trans::Pop;
+    }
+
+}
+
+impl SimpleState for ShipSelectState {
+
+    fn handle_event(&mut self, data: StateData<GameData>, event: StateEvent) -> SimpleTrans {
+        if let StateEvent::Window(event) = &event {
+            if input::is_key_down(&event, VirtualKeyCode::Escape) {
+                return self.go_back(data.world);
+            } else if input::is_key_down(&event, VirtualKeyCode::Left) {
+                self.change_ship(data.world, -1);
+                return Trans::None;
+            } else if input::is_key_down(&event, VirtualKeyCode::Right) {
+                self.change_ship(data.world, 1);
+                return Trans::None;
+            }
+        }
+
+        return match self.menu.handle_event(data.world, &event) {
+            Some(ShipSelectAction::Select) => self.select_ship(data.world),
+            Some(ShipSelectAction::Back) => self.go_back(data.world),
+            Some(ShipSelectAction::Previous) => {
+                self.change_ship(data.world, -1);
+                Trans::None
+            },
+            Some(ShipSelectAction::Next) => {
+                self.change_ship(data.world, 1);
+                Trans::None
+            },
+            None => Trans::None
+        };
+    }
+
+    fn on_start(&mut self, data: StateData<GameData>) {
+        self.load_ships(data.world);
+        self.camera = Some(ShipSelectState::create_camera(data.world));
+        self.refresh_ship(data.world);
+    }
+
+    fn on_stop(&mut self, data: StateData<GameData>) {
+        self.clear_menu(data.world);
+    }
+
+}
@@ -2,6 +2,7 @@ use amethyst::{
     core::Transform,
     ecs::{
         Join,
+        ReadExpect,
         ReadStorage,
         WriteStorage,
         System
@@ -13,7 +14,7 @@ use crate::{
         Rect,
         tags::BoundInArenaTag
     },
-    constants
+    resources::GameRules
 };
 
 pub struct BoundInArenaSystem;
@@ -22,10 +23,11 @@ impl<'s> System<'s> for BoundInArenaSystem {
     type SystemData = (
         WriteStorage<'s, Transform>,
         ReadStorage<'s, Rect>,
-        ReadStorage<'s, BoundInArenaTag>
+        ReadStorage<'s, BoundInArenaTag>,
+        ReadExpect<'s, GameRules>
     );
 
-    fn run(&mut self, (mut transforms, rects, bound_in_arena_tags): Self::SystemData) {
+    fn run(&mut self, (mut transforms, rects, bound_in_arena_tags, game_rules): Self::SystemData) {
         for (transform, rect, _) in (&mut transforms, &rects, &bound_in_arena_tags).join() {
             let half_rect_width = rect.width / 2.0;
             let half_rect_height = rect.height / 2.0;
@@ -33,15 +35,15 @@ impl<'s> System<'s> for BoundInArenaSystem {
             let x = transform.translation().x;
             if x < half_rect_width {
                 transform.set_x(half_rect_width);
-            } else if x > constants::ARENA_WIDTH - half_rect_width {
-                transform.set_x(constants::ARENA_WIDTH - half_rect_width);
+            } else if x > game_rules.arena_width - half_rect_width {
+                transform.set_x(game_rules.arena_width - half_rect_width);
             }
         
             let y = transform.translation().y;
             if y < half_rect_height {
                 transform.set_y(half_rect_height);
-            } else if y > constants::ARENA_HEIGHT - half_rect_height {
-                transform.set_y(constants::ARENA_HEIGHT - half_rect_height);
+            } else if y > game_rules.arena_height - half_rect_height {
+                transform.set_y(game_rules.arena_height - half_rect_height);
             }
         }
     }
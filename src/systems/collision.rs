@@ -0,0 +1,137 @@
+use std::collections::HashSet;
+use amethyst::{
+    core::{
+        nalgebra::Vector2,
+        Transform
+    },
+    ecs::{
+        Entities,
+        Entity,
+        Join,
+        Read,
+        ReadExpect,
+        ReadStorage,
+        System,
+        Write
+    },
+    shrev::EventChannel
+};
+use crate::{
+    components::{
+        CollisionLayer,
+        Rect,
+        hitbox::{
+            Hitbox,
+            HitboxShape
+        }
+    },
+    events::{
+        Collision,
+        CollisionEvent
+    },
+    resources::{
+        CollisionGrid,
+        CollisionMatrix
+    }
+};
+
+#[derive(Default)]
+pub struct CollisionSystem {
+    active_collisions: HashSet<Collision>
+}
+
+impl CollisionSystem {
+
+    // Broadphase already compared Rects, precise check is needed only if any side has a Hitbox
+    fn is_hitboxes_colliding<'s>(
+        entity: Entity,
+        other_entity: Entity,
+        hitboxes: &ReadStorage<'s, Hitbox>,
+        rects: &ReadStorage<'s, Rect>,
+        transforms: &ReadStorage<'s, Transform>
+    ) -> bool {
+        let hitbox = hitboxes.get(entity);
+        let other_hitbox = hitboxes.get(other_entity);
+        if hitbox.is_none() && other_hitbox.is_none() {
+            return true;
+        }
+
+        let get_shapes = |hitbox: Option<&Hitbox>, entity: Entity| -> Vec<HitboxShape> {
+            match hitbox {
+                Some(hitbox) => hitbox.shapes.clone(),
+                None => vec![HitboxShape::from_rect(rects.get(entity).unwrap())]
+            }
+        };
+        let get_position = |entity: Entity| -> Vector2<f32> {
+            let translation = transforms.get(entity).unwrap().translation();
+            Vector2::new(translation.x, translation.y)
+        };
+
+        return Hitbox::is_colliding(
+            &get_shapes(hitbox, entity),
+            get_position(entity),
+            &get_shapes(other_hitbox, other_entity),
+            get_position(other_entity)
+        );
+    }
+
+}
+
+impl<'s> System<'s> for CollisionSystem {
+    type SystemData = (
+        ReadStorage<'s, CollisionLayer>,
+        ReadStorage<'s, Hitbox>,
+        ReadStorage<'s, Rect>,
+        ReadStorage<'s, Transform>,
+        Read<'s, CollisionGrid>,
+        ReadExpect<'s, CollisionMatrix>,
+        Write<'s, EventChannel<CollisionEvent>>,
+        Entities<'s>
+    );
+
+    fn run(
+        &mut self,
+        (
+            collision_layers,
+            hitboxes,
+            rects,
+            transforms,
+            collision_grid,
+            collision_matrix,
+            mut collision_events,
+            entities
+        ): Self::SystemData
+    ) {
+        let mut collisions: HashSet<Collision> = HashSet::new();
+        for (collision_layer, entity) in (&collision_layers, &entities).join() {
+            for other_entity in collision_grid.query_entity(entity) {
+                // Every pair is found from both sides, check it once
+                if other_entity < entity {
+                    continue;
+                }
+
+                if let Some(other_collision_layer) = collision_layers.get(other_entity) {
+                    if
+                        collision_matrix.is_interacting(*collision_layer, *other_collision_layer) &&
+                        CollisionSystem::is_hitboxes_colliding(entity, other_entity, &hitboxes, &rects, &transforms)
+                    {
+                        collisions.insert(Collision::new(entity, *collision_layer, other_entity, *other_collision_layer));
+                    }
+                }
+            }
+        }
+
+        let mut events: Vec<CollisionEvent> = collisions
+            .difference(&self.active_collisions)
+            .map(|collision| CollisionEvent::CollisionStarted(*collision))
+            .collect();
+        events.extend(
+            self.active_collisions
+                .difference(&collisions)
+                .map(|collision| CollisionEvent::CollisionEnded(*collision))
+        );
+        collision_events.iter_write(events);
+
+        self.active_collisions = collisions;
+    }
+}
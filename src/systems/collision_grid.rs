@@ -0,0 +1,33 @@
+use amethyst::{
+    core::Transform,
+    ecs::{
+        Entities,
+        Join,
+        ReadStorage,
+        System,
+        Write
+    }
+};
+use crate::{
+    components::Rect,
+    resources::CollisionGrid,
+    utils::Aabb
+};
+
+pub struct CollisionGridSystem;
+
+impl<'s> System<'s> for CollisionGridSystem {
+    type SystemData = (
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, Rect>,
+        Write<'s, CollisionGrid>,
+        Entities<'s>
+    );
+
+    fn run(&mut self, (transforms, rects, mut collision_grid, entities): Self::SystemData) {
+        collision_grid.clear();
+        for (transform, rect, entity) in (&transforms, &rects, &entities).join() {
+            collision_grid.insert(entity, Aabb::new(rect, transform));
+        }
+    }
+}
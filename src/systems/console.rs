@@ -0,0 +1,229 @@
+use amethyst::{
+    assets::{
+        AssetStorage,
+        Handle,
+        Loader
+    },
+    ecs::{
+        Entities,
+        Entity,
+        Read,
+        ReadExpect,
+        Resources,
+        System,
+        SystemData,
+        Write,
+        WriteStorage
+    },
+    renderer::{
+        Texture,
+        TextureData
+    },
+    shrev::{
+        EventChannel,
+        ReaderId
+    },
+    ui::{
+        Anchor,
+        UiImage,
+        UiText,
+        UiTransform
+    },
+    winit::{
+        ElementState,
+        Event,
+        KeyboardInput,
+        VirtualKeyCode,
+        WindowEvent
+    }
+};
+use crate::{
+    constants,
+    resources::{
+        AssetRegistry,
+        Console
+    }
+};
+
+const OUTPUT_LINES: usize = 10;
+const LINE_HEIGHT: f32 = 18.0;
+const CONSOLE_WIDTH: f32 = 700.0;
+
+// Opens the developer console with ~ and edits its input line, commands are executed by the
+// gameplay console command system
+#[derive(Default)]
+pub struct ConsoleSystem {
+    event_reader: Option<ReaderId<Event>>,
+    background_texture: Option<Handle<Texture>>,
+    ui_elements: Vec<Entity>,
+    text_lines: Vec<Entity>
+}
+
+impl ConsoleSystem {
+
+    fn handle_event(console: &mut Console, event: &Event) {
+        let event = match event {
+            Event::WindowEvent { event, .. } => event,
+            _ => return
+        };
+
+        match event {
+            WindowEvent::KeyboardInput {
+                input: KeyboardInput { state: ElementState::Pressed, virtual_keycode: Some(key_code), .. },
+                ..
+            } => {
+                match key_code {
+                    VirtualKeyCode::Grave => {
+                        console.is_open = !console.is_open;
+                    },
+                    VirtualKeyCode::Return if console.is_open => console.submit(),
+                    VirtualKeyCode::Back if console.is_open => {
+                        console.input.pop();
+                    },
+                    VirtualKeyCode::Up if console.is_open => console.previous_history(),
+                    VirtualKeyCode::Down if console.is_open => console.next_history(),
+                    _ => {}
+                }
+            },
+            // Toggle key characters are not part of commands
+            WindowEvent::ReceivedCharacter(character) if console.is_open => {
+                if !character.is_control() && *character != '`' && *character != '~' {
+                    console.input.push(*character);
+                }
+            },
+            _ => {}
+        }
+    }
+
+    fn clear(&mut self, entities: &Entities) {
+        for element in self.ui_elements.drain(..) {
+            let _ = entities.delete(element);
+        }
+        self.text_lines.clear();
+    }
+
+    fn create_ui(
+        &mut self,
+        asset_registry: &AssetRegistry,
+        background_texture: Handle<Texture>,
+        entities: &Entities,
+        ui_images: &mut WriteStorage<UiImage>,
+        ui_texts: &mut WriteStorage<UiText>,
+        ui_transforms: &mut WriteStorage<UiTransform>
+    ) {
+        let height = LINE_HEIGHT * (OUTPUT_LINES + 1) as f32 + LINE_HEIGHT / 2.0;
+        let background = entities.create();
+        ui_images
+            .insert(background, UiImage { texture: background_texture })
+            .expect("Could not create UiImage!");
+        ui_transforms
+            .insert(background, UiTransform::new(
+                String::from("console_background"),
+                Anchor::TopMiddle,
+                0.0,
+                -height / 2.0,
+                90.0,
+                CONSOLE_WIDTH,
+                height,
+                1
+            ))
+            .expect("Could not create UiTransform!");
+        self.ui_elements.push(background);
+
+        // Output lines first, the input line is the last one
+        let font = asset_registry.get_font(constants::UI_FONT);
+        for index in 0..=OUTPUT_LINES {
+            let text = entities.create();
+            let mut ui_text = UiText::new(font.clone(), String::new(), constants::UI_FONT_COLOR, constants::UI_DEBUG_FONT_SIZE);
+            ui_text.align = Anchor::MiddleLeft;
+            ui_texts
+                .insert(text, ui_text)
+                .expect("Could not create UiText!");
+            ui_transforms
+                .insert(text, UiTransform::new(
+                    format!("console_line_{}", index),
+                    Anchor::TopMiddle,
+                    0.0,
+                    -LINE_HEIGHT * (index as f32 + 0.75),
+                    91.0,
+                    CONSOLE_WIDTH - 20.0,
+                    LINE_HEIGHT,
+                    1
+                ).as_transparent())
+                .expect("Could not create UiTransform!");
+            self.ui_elements.push(text);
+            self.text_lines.push(text);
+        }
+    }
+
+}
+
+impl<'s> System<'s> for ConsoleSystem {
+    type SystemData = (
+        WriteStorage<'s, UiImage>,
+        WriteStorage<'s, UiText>,
+        WriteStorage<'s, UiTransform>,
+        Entities<'s>,
+        Write<'s, Console>,
+        Read<'s, EventChannel<Event>>,
+        Option<ReadExpect<'s, AssetRegistry>>,
+        ReadExpect<'s, Loader>,
+        Read<'s, AssetStorage<Texture>>
+    );
+
+    fn setup(&mut self, res: &mut Resources) {
+        Self::SystemData::setup(res);
+        self.event_reader = Some(res.fetch_mut::<EventChannel<Event>>().register_reader());
+    }
+
+    fn run(
+        &mut self,
+        (
+            mut ui_images,
+            mut ui_texts,
+            mut ui_transforms,
+            entities,
+            mut console,
+            events,
+            asset_registry,
+            loader,
+            texture_storage
+        ): Self::SystemData
+    ) {
+        for event in events.read(self.event_reader.as_mut().unwrap()) {
+            ConsoleSystem::handle_event(&mut console, event);
+        }
+
+        // Assets are only available once loading is done
+        let asset_registry = match (&asset_registry, console.is_open) {
+            (Some(asset_registry), true) => asset_registry,
+            _ => {
+                self.clear(&entities);
+                return;
+            }
+        };
+
+        // States deleting all entities also delete the console
+        if self.ui_elements.iter().any(|element| !entities.is_alive(*element)) {
+            self.clear(&entities);
+        }
+        if self.ui_elements.is_empty() {
+            let background_texture = self.background_texture
+                .get_or_insert_with(|| loader.load_from_data(TextureData::from(constants::CONSOLE_BACKGROUND_COLOR), (), &texture_storage))
+                .clone();
+            self.create_ui(asset_registry, background_texture, &entities, &mut ui_images, &mut ui_texts, &mut ui_transforms);
+        }
+
+        let output = console.get_last_output(OUTPUT_LINES);
+        let lines = (0..OUTPUT_LINES - output.len())
+            .map(|_| String::new())
+            .chain(output.iter().cloned())
+            .chain(std::iter::once(format!("> {}_", console.input)));
+        for (text, line) in self.text_lines.iter().zip(lines) {
+            if let Some(ui_text) = ui_texts.get_mut(*text) {
+                ui_text.text = line;
+            }
+        }
+    }
+
+}
@@ -0,0 +1,185 @@
+use amethyst::{
+    core::Time,
+    ecs::{
+        Entities,
+        Join,
+        ReadExpect,
+        ReadStorage,
+        System,
+        Write,
+        WriteExpect,
+        WriteStorage
+    }
+};
+use rand::prelude::*;
+use crate::{
+    components::{
+        Shield,
+        tags::{
+            DeleteEntityTag,
+            InvulnerableTag,
+            PlayerShipTag
+        }
+    },
+    prefabs::{
+        self,
+        EnemyPrefabs,
+        EnemyPrefabSystemData,
+        PlayerShipPrefabs,
+        SimplePrefab
+    },
+    resources::{
+        Console,
+        ConsoleCommand,
+        GameplaySessionData,
+        GameRules,
+        SpriteSheets
+    }
+};
+
+// Executes the commands submitted to the developer console, runs with the gameplay so commands
+// always act on a running game
+pub struct ConsoleCommandSystem;
+
+impl<'s> System<'s> for ConsoleCommandSystem {
+    type SystemData = (
+        EnemyPrefabSystemData<'s>,
+        ReadStorage<'s, PlayerShipTag>,
+        WriteStorage<'s, Shield>,
+        WriteStorage<'s, InvulnerableTag>,
+        WriteStorage<'s, DeleteEntityTag>,
+        Entities<'s>,
+        Write<'s, Console>,
+        Write<'s, EnemyPrefabs>,
+        Write<'s, PlayerShipPrefabs>,
+        WriteExpect<'s, GameplaySessionData>,
+        ReadExpect<'s, GameRules>,
+        Write<'s, Time>
+    );
+
+    fn run(
+        &mut self,
+        (
+            enemy_system_data,
+            player_ship_tags,
+            mut shields,
+            mut invulnerable_tags,
+            mut delete_entity_tags,
+            entities,
+            mut console,
+            mut enemy_prefabs,
+            mut player_ship_prefabs,
+            mut session_data,
+            game_rules,
+            mut time
+        ): Self::SystemData
+    ) {
+        // Rebound so the storages can be borrowed for less than the system lifetime
+        let mut enemy_system_data = enemy_system_data;
+
+        for command in console.take_commands() {
+            match command {
+                ConsoleCommand::Spawn { prefab, x } => {
+                    let enemy_prefab = match enemy_prefabs.get(&prefab) {
+                        Some(enemy_prefab) => enemy_prefab,
+                        None => {
+                            console.print(format!("unknown prefab {}, available: {}", prefab, enemy_prefabs.names().join(", ")));
+                            continue;
+                        }
+                    };
+                    let half_width = enemy_prefab.width / 2.0;
+                    let x = x.unwrap_or_else(|| rand::thread_rng().gen_range(half_width, game_rules.arena_width - half_width));
+                    // -1.0 so it's not deleted by out of bounds system
+                    let y = game_rules.arena_height + enemy_prefab.height - 1.0;
+                    enemy_prefab.create_entity(&entities, x, y, &mut enemy_system_data);
+                    console.print(format!("spawned {} at {}", prefab, x));
+                },
+                ConsoleCommand::Give { pickup } => {
+                    let killables = &mut enemy_system_data.killables;
+                    for (entity, _) in (&entities, &player_ship_tags).join() {
+                        match pickup.as_str() {
+                            "health" => {
+                                if let Some(killable) = killables.get_mut(entity) {
+                                    killable.gain_health();
+                                }
+                            },
+                            "shield" => {
+                                match shields.get_mut(entity) {
+                                    Some(shield) => shield.add_charges(1),
+                                    None => {
+                                        shields
+                                            .insert(entity, Shield::new(1))
+                                            .expect("Could not create Shield!");
+                                    }
+                                }
+                            },
+                            _ => {}
+                        }
+                    }
+                    match pickup.as_str() {
+                        "health" | "shield" => console.print(format!("gave {} to every player", pickup)),
+                        _ => console.print(format!("unknown pickup {}, available: health, shield", pickup))
+                    }
+                },
+                ConsoleCommand::God => {
+                    let is_enabled = (&player_ship_tags, !&invulnerable_tags).join().next().is_some();
+                    for (entity, _) in (&entities, &player_ship_tags).join() {
+                        if is_enabled {
+                            let _ = invulnerable_tags.insert(entity, InvulnerableTag);
+                        } else {
+                            invulnerable_tags.remove(entity);
+                        }
+                    }
+                    console.print(format!("god mode {}", if is_enabled { "on" } else { "off" }));
+                },
+                ConsoleCommand::SetScore { score, player_index } => {
+                    if player_index < session_data.get_player_count() {
+                        session_data.set_score(player_index, score);
+                        console.print(format!("score of player {} set to {}", player_index + 1, score));
+                    } else {
+                        console.print(format!("there is no player {}", player_index + 1));
+                    }
+                },
+                ConsoleCommand::TimeScale(time_scale) => {
+                    session_data.set_time_scale(time_scale);
+                    time.set_time_scale(time_scale);
+                    console.print(format!("timescale set to {}", time_scale));
+                },
+                ConsoleCommand::KillAll => {
+                    let enemy_tags = &enemy_system_data.enemy_tags;
+                    let mut count = 0;
+                    for (entity, _) in (&entities, enemy_tags).join() {
+                        let _ = delete_entity_tags.insert(entity, DeleteEntityTag);
+                        count += 1;
+                    }
+                    console.print(format!("removed {} enemies", count));
+                },
+                ConsoleCommand::ReloadPrefabs => {
+                    let sprite_sheets: &SpriteSheets = &enemy_system_data.sprite_sheets;
+                    let mut errors = Vec::new();
+                    let loaded_player_ship_prefabs = prefabs::load_player_ship_prefabs(sprite_sheets.get_names(), &mut errors);
+                    // Enemy prefabs are only swapped once the player ships loaded as well
+                    if errors.is_empty() {
+                        if let Err(enemy_errors) = prefabs::reload_enemy_prefabs(
+                            &mut enemy_prefabs,
+                            sprite_sheets.get_names(),
+                            &game_rules.enemy_category_weights
+                        ) {
+                            errors = enemy_errors;
+                        }
+                    }
+                    if errors.is_empty() {
+                        *player_ship_prefabs = loaded_player_ship_prefabs;
+                        console.print(String::from("prefabs reloaded"));
+                    } else {
+                        console.print(String::from("could not reload prefabs, keeping previous versions:"));
+                        for error in errors {
+                            console.print(error.to_string());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+}
@@ -0,0 +1,110 @@
+use amethyst::{
+    ecs::{
+        Read,
+        ReadStorage,
+        Resources,
+        System,
+        SystemData,
+        WriteStorage
+    },
+    shrev::{
+        EventChannel,
+        ReaderId
+    }
+};
+use crate::{
+    components::{
+        CollisionLayer,
+        Killable,
+        Missile,
+        Shield,
+        tags::{
+            DeleteEntityTag,
+            InvulnerableTag
+        }
+    },
+    events::CollisionEvent
+};
+
+#[derive(Default)]
+pub struct DamageSystem {
+    collision_event_reader: Option<ReaderId<CollisionEvent>>
+}
+
+impl<'s> System<'s> for DamageSystem {
+    type SystemData = (
+        ReadStorage<'s, Missile>,
+        WriteStorage<'s, Killable>,
+        WriteStorage<'s, Shield>,
+        WriteStorage<'s, DeleteEntityTag>,
+        ReadStorage<'s, InvulnerableTag>,
+        Read<'s, EventChannel<CollisionEvent>>
+    );
+
+    fn setup(&mut self, res: &mut Resources) {
+        Self::SystemData::setup(res);
+        self.collision_event_reader = Some(res.fetch_mut::<EventChannel<CollisionEvent>>().register_reader());
+    }
+
+    fn run(
+        &mut self,
+        (
+            missiles,
+            mut killables,
+            mut shields,
+            mut delete_entity_tags,
+            invulnerable_tags,
+            collision_events
+        ): Self::SystemData
+    ) {
+        for event in collision_events.read(self.collision_event_reader.as_mut().unwrap()) {
+            let collision = match event {
+                CollisionEvent::CollisionStarted(collision) => collision,
+                CollisionEvent::CollisionEnded(_) => continue
+            };
+
+            let missile_hit = collision
+                .get_entities(CollisionLayer::PlayerProjectile, CollisionLayer::Enemy)
+                .or_else(|| collision.get_entities(CollisionLayer::EnemyProjectile, CollisionLayer::Player));
+            if let Some((missile_entity, target_entity)) = missile_hit {
+                // Missile could have already collided with something during this frame
+                if delete_entity_tags.contains(missile_entity) {
+                    continue;
+                }
+
+                if let Some(target_killable) = killables.get_mut(target_entity) {
+                    // Target could have already died, no need to waste the missile
+                    if target_killable.is_alive() {
+                        let _ = delete_entity_tags.insert(missile_entity, DeleteEntityTag);
+                        if invulnerable_tags.contains(target_entity) {
+                            continue;
+                        }
+                        if shields.get_mut(target_entity).map_or(false, |shield| shield.absorb_hit()) {
+                            continue;
+                        }
+
+                        // TODO: Explosion animation
+                        match missiles.get(missile_entity).and_then(|missile| missile.get_player_index()) {
+                            Some(player_index) => target_killable.deal_damage_by_player(player_index),
+                            None => target_killable.deal_damage()
+                        }
+                    }
+                }
+            } else if let Some((player_entity, enemy_entity)) = collision.get_entities(CollisionLayer::Player, CollisionLayer::Enemy) {
+                if delete_entity_tags.contains(player_entity) || delete_entity_tags.contains(enemy_entity) {
+                    continue;
+                }
+
+                if let Some(player_killable) = killables.get_mut(player_entity) {
+                    let _ = delete_entity_tags.insert(enemy_entity, DeleteEntityTag);
+                    if invulnerable_tags.contains(player_entity) {
+                        continue;
+                    }
+                    if !shields.get_mut(player_entity).map_or(false, |shield| shield.absorb_hit()) {
+                        player_killable.deal_damage();
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use amethyst::{
+    core::{
+        nalgebra::Vector2,
+        Time,
+        Transform
+    },
+    ecs::{
+        Entities,
+        Join,
+        Read,
+        ReadStorage,
+        System,
+        WriteStorage
+    },
+    renderer::SpriteRender
+};
+use rand::prelude::*;
+use std::f32::consts::PI;
+use crate::components::{
+    Expire,
+    Killable,
+    RigidBody,
+    tags::DeleteEntityTag
+};
+
+const DEBRIS_COUNT: usize = 4;
+const DEBRIS_SCALE: f32 = 0.35;
+const DEBRIS_LIFETIME: f64 = 1.2;
+
+pub struct DebrisSystem;
+
+impl<'s> System<'s> for DebrisSystem {
+    type SystemData = (
+        ReadStorage<'s, Killable>,
+        ReadStorage<'s, DeleteEntityTag>,
+        WriteStorage<'s, Expire>,
+        WriteStorage<'s, RigidBody>,
+        WriteStorage<'s, SpriteRender>,
+        WriteStorage<'s, Transform>,
+        Entities<'s>,
+        Read<'s, Time>
+    );
+
+    fn run(
+        &mut self,
+        (
+            killables,
+            delete_entity_tags,
+            mut expires,
+            mut rigid_bodies,
+            mut sprite_renders,
+            mut transforms,
+            entities,
+            time
+        ): Self::SystemData
+    ) {
+        let mut rng = rand::thread_rng();
+        let mut debris = Vec::new();
+        // Ships marked for deletion this tick because they were destroyed, not because they left the arena
+        for (killable, transform, sprite_render, entity, _) in (&killables, &transforms, &sprite_renders, &entities, &delete_entity_tags).join() {
+            if killable.is_alive() {
+                continue;
+            }
+
+            let velocity = rigid_bodies
+                .get(entity)
+                .map(|rigid_body| rigid_body.velocity)
+                .unwrap_or(Vector2::new(0.0, 0.0));
+            for _ in 0..DEBRIS_COUNT {
+                let angle = rng.gen_range(0.0, 2.0 * PI);
+                let speed = rng.gen_range(60.0, 140.0);
+
+                let mut debris_transform = Transform::default();
+                debris_transform.set_xyz(transform.translation().x, transform.translation().y, 0.0);
+                debris_transform.set_scale(
+                    transform.scale().x * DEBRIS_SCALE,
+                    transform.scale().y * DEBRIS_SCALE,
+                    1.0
+                );
+
+                let mut debris_rigid_body = RigidBody::new(0.2);
+                debris_rigid_body.velocity = velocity + Vector2::new(angle.cos(), angle.sin()) * speed;
+                debris_rigid_body.angular_velocity = rng.gen_range(-6.0, 6.0);
+                debris_rigid_body.linear_damping = 1.0;
+
+                debris.push((debris_transform, debris_rigid_body, sprite_render.clone()));
+            }
+        }
+
+        for (debris_transform, debris_rigid_body, sprite_render) in debris {
+            entities
+                .build_entity()
+                .with(debris_transform, &mut transforms)
+                .with(debris_rigid_body, &mut rigid_bodies)
+                .with(sprite_render, &mut sprite_renders)
+                .with(Expire::new(DEBRIS_LIFETIME, time.absolute_time_seconds()), &mut expires)
+                .build();
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+use amethyst::{
+    assets::Handle,
+    core::{
+        Transform,
+        nalgebra::Vector2
+    },
+    ecs::{
+        Entities,
+        Entity,
+        Join,
+        Read,
+        ReadExpect,
+        ReadStorage,
+        System,
+        WriteExpect,
+        WriteStorage
+    },
+    input::InputHandler,
+    renderer::{
+        DebugLines,
+        Rgba
+    },
+    ui::{
+        Anchor,
+        FontAsset,
+        UiText,
+        UiTransform
+    }
+};
+use crate::{
+    components::{
+        Killable,
+        Missile,
+        Rect,
+        hitbox::Hitbox,
+        tags::{
+            EnemyTag,
+            HealthPickupTag,
+            PlayerShipTag
+        }
+    },
+    constants,
+    resources::{
+        ArenaViewport,
+        AssetRegistry,
+        SystemTimings
+    }
+};
+
+const PLAYER_COLOR: Rgba = Rgba(0.2, 0.9, 0.2, 1.0);
+const ENEMY_COLOR: Rgba = Rgba(0.9, 0.2, 0.2, 1.0);
+const MISSILE_COLOR: Rgba = Rgba(0.9, 0.9, 0.2, 1.0);
+const PICKUP_COLOR: Rgba = Rgba(0.2, 0.8, 0.9, 1.0);
+const OTHER_COLOR: Rgba = Rgba(0.8, 0.8, 0.8, 1.0);
+const LINE_HEIGHT: f32 = 16.0;
+const TEXT_WIDTH: f32 = 300.0;
+
+// Toggled with F3, outlines every Hitbox or Rect, labels Killable health and lists entity count and
+// gameplay system timings
+#[derive(Default)]
+pub struct DebugOverlaySystem {
+    is_enabled: bool,
+    was_toggle_key_down: bool,
+    stat_texts: Vec<Entity>,
+    health_labels: HashMap<Entity, Entity>
+}
+
+impl DebugOverlaySystem {
+
+    fn clear(&mut self, entities: &Entities) {
+        for text in self.stat_texts.drain(..).chain(self.health_labels.drain().map(|(_, label)| label)) {
+            let _ = entities.delete(text);
+        }
+    }
+
+    fn create_text(
+        id: String,
+        font: Handle<FontAsset>,
+        anchor: Anchor,
+        entities: &Entities,
+        ui_texts: &mut WriteStorage<UiText>,
+        ui_transforms: &mut WriteStorage<UiTransform>
+    ) -> Entity {
+        let text = entities.create();
+        ui_texts
+            .insert(text, UiText::new(font, String::new(), constants::UI_FONT_COLOR, constants::UI_DEBUG_FONT_SIZE))
+            .expect("Could not create UiText!");
+        ui_transforms
+            .insert(text, UiTransform::new(id, anchor, 0.0, 0.0, 50.0, TEXT_WIDTH, LINE_HEIGHT, 1).as_transparent())
+            .expect("Could not create UiTransform!");
+        return text;
+    }
+
+    fn draw_rect(debug_lines: &mut DebugLines, x: f32, y: f32, rect: &Rect, color: Rgba) {
+        let (left, right) = (x - rect.width / 2.0, x + rect.width / 2.0);
+        let (bottom, top) = (y - rect.height / 2.0, y + rect.height / 2.0);
+        debug_lines.draw_line([left, bottom, 0.0], [right, bottom, 0.0], color);
+        debug_lines.draw_line([right, bottom, 0.0], [right, top, 0.0], color);
+        debug_lines.draw_line([right, top, 0.0], [left, top, 0.0], color);
+        debug_lines.draw_line([left, top, 0.0], [left, bottom, 0.0], color);
+    }
+
+    fn draw_outline(debug_lines: &mut DebugLines, points: &[Vector2<f32>], color: Rgba) {
+        for (i, point) in points.iter().enumerate() {
+            let next_point = points[(i + 1) % points.len()];
+            debug_lines.draw_line([point.x, point.y, 0.0], [next_point.x, next_point.y, 0.0], color);
+        }
+    }
+
+}
+
+impl<'s> System<'s> for DebugOverlaySystem {
+    type SystemData = (
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, Rect>,
+        ReadStorage<'s, Hitbox>,
+        ReadStorage<'s, Killable>,
+        ReadStorage<'s, PlayerShipTag>,
+        ReadStorage<'s, EnemyTag>,
+        ReadStorage<'s, Missile>,
+        ReadStorage<'s, HealthPickupTag>,
+        WriteStorage<'s, UiText>,
+        WriteStorage<'s, UiTransform>,
+        Entities<'s>,
+        WriteExpect<'s, DebugLines>,
+        Option<ReadExpect<'s, AssetRegistry>>,
+        Read<'s, SystemTimings>,
+        Read<'s, ArenaViewport>,
+        Read<'s, InputHandler<String, String>>
+    );
+
+    fn run(
+        &mut self,
+        (
+            transforms,
+            rects,
+            hitboxes,
+            killables,
+            player_ship_tags,
+            enemy_tags,
+            missiles,
+            health_pickup_tags,
+            mut ui_texts,
+            mut ui_transforms,
+            entities,
+            mut debug_lines,
+            asset_registry,
+            system_timings,
+            arena_viewport,
+            input
+        ): Self::SystemData
+    ) {
+        let is_toggle_key_down = input.action_is_down(constants::DEBUG_OVERLAY_BINDING).unwrap_or(false);
+        if is_toggle_key_down && !self.was_toggle_key_down {
+            self.is_enabled = !self.is_enabled;
+            if !self.is_enabled {
+                self.clear(&entities);
+            }
+        }
+        self.was_toggle_key_down = is_toggle_key_down;
+
+        // Assets are only available once loading is done
+        let font = match (&asset_registry, self.is_enabled) {
+            (Some(asset_registry), true) => asset_registry.get_font(constants::UI_FONT),
+            _ => return
+        };
+
+        // States deleting all entities also delete the overlay texts
+        self.stat_texts.retain(|text| entities.is_alive(*text));
+        self.health_labels.retain(|entity, label| {
+            if entities.is_alive(*entity) && killables.contains(*entity) {
+                return entities.is_alive(*label);
+            }
+            let _ = entities.delete(*label);
+            return false;
+        });
+
+        // Hitbox shapes are what collisions use, the Rect is only shown for entities without one
+        for (entity, transform, rect, hitbox) in (&entities, &transforms, &rects, hitboxes.maybe()).join() {
+            let color = if player_ship_tags.contains(entity) {
+                PLAYER_COLOR
+            } else if enemy_tags.contains(entity) {
+                ENEMY_COLOR
+            } else if missiles.contains(entity) {
+                MISSILE_COLOR
+            } else if health_pickup_tags.contains(entity) {
+                PICKUP_COLOR
+            } else {
+                OTHER_COLOR
+            };
+            let translation = transform.translation();
+            match hitbox {
+                Some(hitbox) => {
+                    let position = Vector2::new(translation.x, translation.y);
+                    for shape in &hitbox.shapes {
+                        DebugOverlaySystem::draw_outline(&mut debug_lines, &shape.get_outline(position), color);
+                    }
+                },
+                None => DebugOverlaySystem::draw_rect(&mut debug_lines, translation.x, translation.y, rect, color)
+            }
+        }
+
+        for (entity, transform, killable) in (&entities, &transforms, &killables).join() {
+            let label = *self.health_labels.entry(entity).or_insert_with(|| {
+                DebugOverlaySystem::create_text(
+                    format!("debug_health_{}", entity.id()),
+                    font.clone(),
+                    Anchor::BottomLeft,
+                    &entities,
+                    &mut ui_texts,
+                    &mut ui_transforms
+                )
+            });
+            let (x, y) = arena_viewport.to_screen(transform.translation().x, transform.translation().y);
+            if let Some(ui_transform) = ui_transforms.get_mut(label) {
+                ui_transform.local_x = x;
+                ui_transform.local_y = y;
+            }
+            if let Some(ui_text) = ui_texts.get_mut(label) {
+                ui_text.text = format!("{}/{}", killable.get_health(), killable.get_max_health());
+            }
+        }
+
+        let mut lines = vec![format!("Entities: {}", (&entities).join().count())];
+        lines.extend(
+            system_timings
+                .get_sorted()
+                .into_iter()
+                .map(|(name, duration)| format!("{}: {:.3} ms", name, duration.as_secs_f64() * 1000.0))
+        );
+        while self.stat_texts.len() < lines.len() {
+            let index = self.stat_texts.len();
+            let text = DebugOverlaySystem::create_text(
+                format!("debug_stat_{}", index),
+                font.clone(),
+                Anchor::TopLeft,
+                &entities,
+                &mut ui_texts,
+                &mut ui_transforms
+            );
+            if let Some(ui_transform) = ui_transforms.get_mut(text) {
+                ui_transform.local_x = TEXT_WIDTH / 2.0 + 10.0;
+                ui_transform.local_y = -LINE_HEIGHT * (index as f32 + 2.5);
+            }
+            self.stat_texts.push(text);
+        }
+        for (text, line) in self.stat_texts.iter().zip(lines.into_iter().chain(std::iter::repeat(String::new()))) {
+            if let Some(ui_text) = ui_texts.get_mut(*text) {
+                ui_text.text = line;
+            }
+        }
+    }
+
+}
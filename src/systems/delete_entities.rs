@@ -1,22 +1,32 @@
-use amethyst::ecs::{
-    Join,
-    ReadStorage,
-    Entities,
-    System
+use amethyst::{
+    core::ParentHierarchy,
+    ecs::{
+        Entities,
+        Join,
+        ReadExpect,
+        ReadStorage,
+        System
+    }
 };
 use crate::components::tags::DeleteEntityTag;
 
+// Deletes tagged entities together with all their children in the transform hierarchy, like
+// cannons and health bars
 pub struct DeleteEntitiesSystem;
 
 impl<'s> System<'s> for DeleteEntitiesSystem {
     type SystemData = (
         ReadStorage<'s, DeleteEntityTag>,
-        Entities<'s>
+        Entities<'s>,
+        ReadExpect<'s, ParentHierarchy>
     );
 
-    fn run(&mut self, (delete_entity_tags, entities): Self::SystemData) {
+    fn run(&mut self, (delete_entity_tags, entities, hierarchy): Self::SystemData) {
         for (_, entity) in (&delete_entity_tags, &entities).join() {
+            for (child, _) in (&entities, &hierarchy.all_children(entity)).join() {
+                let _ = entities.delete(child);
+            }
             let _ = entities.delete(entity);
         }
     }
-}
\ No newline at end of file
+}
@@ -2,6 +2,7 @@ use amethyst::{
     core::Transform,
     ecs::{
         Join,
+        ReadExpect,
         WriteStorage,
         ReadStorage,
         Entities,
@@ -16,7 +17,7 @@ use crate::{
             DestroyOutOfArenaTag
         }
     },
-    constants
+    resources::GameRules
 };
 
 pub struct DestroyOutOfArenaSystem;
@@ -27,7 +28,8 @@ impl<'s> System<'s> for DestroyOutOfArenaSystem {
         WriteStorage<'s, Transform>,
         ReadStorage<'s, Rect>,
         ReadStorage<'s, DestroyOutOfArenaTag>,
-        WriteStorage<'s, DeleteEntityTag>
+        WriteStorage<'s, DeleteEntityTag>,
+        ReadExpect<'s, GameRules>
     );
 
     fn run(
@@ -37,7 +39,8 @@ impl<'s> System<'s> for DestroyOutOfArenaSystem {
             mut transforms, 
             rects, 
             destroy_out_of_arena_tags,
-            mut delete_entity_tags
+            mut delete_entity_tags,
+            game_rules
         ): Self::SystemData
     ) {
         for (entity, transform, rect, _) in (&entities, &mut transforms, &rects, &destroy_out_of_arena_tags).join() {
@@ -46,9 +49,9 @@ impl<'s> System<'s> for DestroyOutOfArenaSystem {
             let y = transform.translation().y;
             if
                 x < -rect.width ||
-                x > constants::ARENA_WIDTH + rect.width ||
+                x > game_rules.arena_width + rect.width ||
                 y < -rect.height ||
-                y > constants::ARENA_HEIGHT + rect.height 
+                y > game_rules.arena_height + rect.height 
             {
                 let _ = delete_entity_tags.insert(entity, DeleteEntityTag);
             }
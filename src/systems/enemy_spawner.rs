@@ -1,40 +1,21 @@
 use amethyst::{
-    core::{
-        Parent,
-        Transform,
-        Time
-    },
+    core::Time,
     ecs:: {
         Entities,
         Read,
         ReadExpect,
-        WriteStorage,
         System
-    },
-    renderer::{
-        SpriteRender,
-        SpriteSheetHandle
     }
 };
+use log::warn;
 use rand::prelude::*;
 use crate::{
-    components::{
-        Cannon,
-        Rect,
-        Moveable,
-        SpaceShip,
-        Killable,
-        tags::{
-            EnemyTag,
-            DestroyOutOfArenaTag
-        }
-    },
-    constants,
     prefabs::{
-        EnemyPrefabData,
         EnemyPrefabs,
+        EnemyPrefabSystemData,
         SimplePrefab
-    }
+    },
+    resources::GameRules
 };
 
 #[derive(Default)]
@@ -42,29 +23,11 @@ pub struct EnemySpawnerSystem {
     next_spawn_time: f64
 }
 
-impl EnemySpawnerSystem {
-
-    fn get_random_enemy_prefab<'a>(enemy_prefabs: &'a Vec<EnemyPrefabData>, rng: &mut ThreadRng) -> &'a EnemyPrefabData {
-        let idx = rng.gen_range(0, enemy_prefabs.len());
-        return & enemy_prefabs.get(idx).unwrap();
-    }
-
-}
-
 impl<'s> System<'s> for EnemySpawnerSystem {
     type SystemData = (
-        WriteStorage<'s, Transform>,
-        WriteStorage<'s, Rect>,
-        WriteStorage<'s, Moveable>,
-        WriteStorage<'s, Killable>,        
-        WriteStorage<'s, SpaceShip>,
-        WriteStorage<'s, Cannon>,
-        WriteStorage<'s, EnemyTag>,
-        WriteStorage<'s, SpriteRender>,
-        WriteStorage<'s, DestroyOutOfArenaTag>,
-        WriteStorage<'s, Parent>,
-        ReadExpect<'s, SpriteSheetHandle>,
+        EnemyPrefabSystemData<'s>,
         ReadExpect<'s, EnemyPrefabs>,
+        ReadExpect<'s, GameRules>,
         Read<'s, Time>,
         Entities<'s>
     );
@@ -72,68 +35,35 @@ impl<'s> System<'s> for EnemySpawnerSystem {
     fn run(
         &mut self, 
         (
-            transforms,
-            rects,
-            moveables,
-            killables,
-            space_ships,
-            cannons,
-            enemy_tags,
-            sprite_renders,
-            destroy_out_of_arena_tags,
-            parents,
-            sprite_sheet_handle,
+            enemy_system_data,
             enemy_prefabs,
+            game_rules,
             time,
             entities
         ): Self::SystemData
     ) {
+        // Rebound so the storages can be borrowed for less than the system lifetime
+        let mut enemy_system_data = enemy_system_data;
         if self.next_spawn_time <= time.absolute_time_seconds() {
             let mut rng = rand::thread_rng();
-            let enemy_type: f64 = rng.gen();
-            let enemy_prefab = {
-                // TODO put enemy type chances somewhere else
-                // TOOD confusing and ugly, refactor
-                if enemy_type < 0.1 && enemy_prefabs.large_enemy_count() > 0 { // 10% chance
-                    Self::get_random_enemy_prefab(&enemy_prefabs.large_enemy_prefabs, &mut rng)
-                } else if enemy_type < 0.4 && enemy_prefabs.medium_enemy_count() > 0 { // 30% chance
-                    Self::get_random_enemy_prefab(&enemy_prefabs.medium_enemy_prefabs, &mut rng)
-                } else if enemy_prefabs.small_enemy_count() > 0 { // 60% chance
-                    Self::get_random_enemy_prefab(&enemy_prefabs.small_enemy_prefabs, &mut rng)
-                } else {
-                    panic!("enemy_spawner, no enemy prefabs were loaded!");
-                }
-            };
-
-            // Position
-            let width = &enemy_prefab.width;
-            let height = &enemy_prefab.height;
-            let x = rng.gen_range(width / 2.0, constants::ARENA_WIDTH - width / 2.0);
-            // -1.0 so it's not deleted by out of bounds system
-            let y = constants::ARENA_HEIGHT + height - 1.0; 
+            // Loading and reloads check for a spawnable category, skip the spawn if it is missing anyway
+            match enemy_prefabs.pick_random(&game_rules.enemy_category_weights, &mut rng) {
+                Some(enemy_prefab) => {
+                    // Position
+                    let width = &enemy_prefab.width;
+                    let height = &enemy_prefab.height;
+                    let x = rng.gen_range(width / 2.0, game_rules.arena_width - width / 2.0);
+                    // -1.0 so it's not deleted by out of bounds system
+                    let y = game_rules.arena_height + height - 1.0; 
 
-            // Spawn enemy
-            enemy_prefab.create_entity(
-                &entities, 
-                x,
-                y,
-                &mut (
-                    transforms,
-                    rects,
-                    moveables,
-                    killables,
-                    space_ships,
-                    cannons,
-                    enemy_tags,
-                    sprite_renders,
-                    destroy_out_of_arena_tags,
-                    parents,
-                    sprite_sheet_handle
-                )
-            );
+                    // Spawn enemy
+                    enemy_prefab.create_entity(&entities, x, y, &mut enemy_system_data);
+                },
+                None => warn!("enemy_spawner, no enemy category of enemy_category_weights can be spawned")
+            }
 
             // Update next spawn time
-            let next_spawn_delay = rand::thread_rng().gen_range(constants::ENEMY_SPAWNER_MIN_DELAY, constants::ENEMY_SPAWNER_MAX_DELAY);
+            let next_spawn_delay = rand::thread_rng().gen_range(game_rules.enemy_spawner_min_delay, game_rules.enemy_spawner_max_delay);
             self.next_spawn_time = time.absolute_time_seconds() + next_spawn_delay;
         }
     }
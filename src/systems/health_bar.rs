@@ -0,0 +1,101 @@
+use amethyst::{
+    core::Transform,
+    ecs::{
+        Join,
+        ReadExpect,
+        ReadStorage,
+        System,
+        WriteStorage
+    },
+    renderer::{
+        Hidden,
+        SpriteRender
+    }
+};
+use crate::{
+    components::{
+        HealthBar,
+        HealthBarMode,
+        Killable
+    },
+    constants,
+    resources::{
+        SpriteRef,
+        SpriteSheets
+    }
+};
+
+// Updates world-space health bars when the health of their owner changes, bars hidden until
+// damaged are shown on the first change
+pub struct HealthBarSystem;
+
+impl<'s> System<'s> for HealthBarSystem {
+    type SystemData = (
+        ReadStorage<'s, Killable>,
+        WriteStorage<'s, HealthBar>,
+        WriteStorage<'s, Transform>,
+        WriteStorage<'s, SpriteRender>,
+        WriteStorage<'s, Hidden>,
+        ReadExpect<'s, SpriteSheets>
+    );
+
+    fn run(
+        &mut self,
+        (
+            killables,
+            mut health_bars,
+            mut transforms,
+            mut sprite_renders,
+            mut hiddens,
+            sprite_sheets
+        ): Self::SystemData
+    ) {
+        for killable in (&killables).join() {
+            let health_bar = match killable.health_bar_entity.and_then(|entity| health_bars.get_mut(entity)) {
+                Some(health_bar) => health_bar,
+                None => continue
+            };
+            let health = killable.get_health().max(0);
+            if health == health_bar.last_health {
+                continue;
+            }
+            health_bar.last_health = health;
+
+            if health_bar.is_hidden {
+                health_bar.is_hidden = false;
+                for sprite_entity in &health_bar.sprite_entities {
+                    hiddens.remove(*sprite_entity);
+                }
+            }
+
+            match health_bar.mode {
+                HealthBarMode::Fill => {
+                    // Shrinks towards the left edge
+                    let ratio = health as f32 / killable.get_max_health() as f32;
+                    for fill_entity in &health_bar.fill_entities {
+                        if let Some(transform) = transforms.get_mut(*fill_entity) {
+                            let y_scale = transform.scale().y;
+                            transform.set_scale(health_bar.inner_width * ratio / constants::HEALTH_BAR_SPRITE_SIZE, y_scale, 1.0);
+                            transform.set_x(-health_bar.inner_width * (1.0 - ratio) / 2.0);
+                        }
+                    }
+                },
+                HealthBarMode::Segments => {
+                    for (i, fill_entity) in health_bar.fill_entities.iter().enumerate() {
+                        let sprite = if (i as i32) < health {
+                            constants::HEALTH_BAR_FILL_SPRITE
+                        } else {
+                            constants::HEALTH_BAR_EMPTY_SPRITE
+                        };
+                        if let Some(sprite_render) = sprite_renders.get_mut(*fill_entity) {
+                            *sprite_render = sprite_sheets
+                                .get_sprite_render(&SpriteRef::new(sprite.0, sprite.1))
+                                .expect("Health bar sprites should have been validated while loading!");
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+}
@@ -0,0 +1,136 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{
+        Path,
+        PathBuf
+    },
+    time::SystemTime
+};
+use amethyst::{
+    config::Config,
+    core::Time,
+    ecs::{
+        Read,
+        ReadExpect,
+        System,
+        Write
+    }
+};
+use log::{
+    info,
+    warn
+};
+use crate::{
+    prefabs::{
+        self,
+        EnemyPrefabs
+    },
+    resources::{
+        CollisionMatrix,
+        GameRules,
+        SpriteSheets
+    }
+};
+
+const ENEMY_PREFABS_DIR: &str = "assets/prefabs/enemies";
+const COLLISION_MATRIX_PATH: &str = "assets/config/collision_matrix.ron";
+const POLL_INTERVAL: f64 = 1.0;
+
+// Dev mode only, polls prefab and config files and swaps resources when they change on disk
+#[derive(Default)]
+pub struct HotReloadSystem {
+    next_poll_time: f64,
+    modified_times: HashMap<PathBuf, SystemTime>,
+    is_initialised: bool
+}
+
+impl HotReloadSystem {
+
+    // Returns changed and deleted files since the last poll, first poll only records current state
+    fn poll_changed_files(&mut self) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = match fs::read_dir(ENEMY_PREFABS_DIR) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .collect(),
+            Err(err) => {
+                warn!("Could not read {}: {}", ENEMY_PREFABS_DIR, err);
+                Vec::new()
+            }
+        };
+        paths.push(PathBuf::from(COLLISION_MATRIX_PATH));
+
+        let mut changed_paths = Vec::new();
+        for path in &paths {
+            let modified_time = match fs::metadata(path).and_then(|metadata| metadata.modified()) {
+                Ok(modified_time) => modified_time,
+                Err(_) => continue
+            };
+            if self.modified_times.get(path) != Some(&modified_time) {
+                self.modified_times.insert(path.clone(), modified_time);
+                if self.is_initialised {
+                    changed_paths.push(path.clone());
+                }
+            }
+        }
+
+        let deleted_paths: Vec<PathBuf> = self.modified_times
+            .keys()
+            .filter(|path| !paths.contains(*path) || !path.exists())
+            .cloned()
+            .collect();
+        for path in deleted_paths {
+            self.modified_times.remove(&path);
+            changed_paths.push(path);
+        }
+        self.is_initialised = true;
+
+        return changed_paths;
+    }
+
+    fn reload_collision_matrix(&mut self, collision_matrix: &mut CollisionMatrix) {
+        match CollisionMatrix::load_no_fallback(COLLISION_MATRIX_PATH) {
+            Ok(loaded_collision_matrix) => {
+                info!("Reloaded {}", COLLISION_MATRIX_PATH);
+                *collision_matrix = loaded_collision_matrix;
+            },
+            Err(err) => warn!("Could not reload {}, keeping previous version: {}", COLLISION_MATRIX_PATH, err)
+        }
+    }
+
+}
+
+impl<'s> System<'s> for HotReloadSystem {
+    type SystemData = (
+        Write<'s, EnemyPrefabs>,
+        Write<'s, CollisionMatrix>,
+        Option<ReadExpect<'s, SpriteSheets>>,
+        ReadExpect<'s, GameRules>,
+        Read<'s, Time>
+    );
+
+    fn run(&mut self, (mut enemy_prefabs, mut collision_matrix, sprite_sheets, game_rules, time): Self::SystemData) {
+        // Sprite sheets only exist once loading is done
+        let sprite_sheets = match sprite_sheets {
+            Some(sprite_sheets) => sprite_sheets,
+            None => return
+        };
+        // Real time, so files are reloaded while the game is paused too
+        if self.next_poll_time > time.absolute_real_time_seconds() {
+            return;
+        }
+        self.next_poll_time = time.absolute_real_time_seconds() + POLL_INTERVAL;
+
+        let changed_paths = self.poll_changed_files();
+        if changed_paths.iter().any(|path| path == Path::new(COLLISION_MATRIX_PATH)) {
+            self.reload_collision_matrix(&mut collision_matrix);
+        }
+        if changed_paths.iter().any(|path| path.extension().map_or(false, |extension| extension == "json")) {
+            // Failed reloads are logged by the prefabs module
+            if prefabs::reload_enemy_prefabs(&mut enemy_prefabs, sprite_sheets.get_names(), &game_rules.enemy_category_weights).is_ok() {
+                info!("Reloaded enemy prefabs from {}", ENEMY_PREFABS_DIR);
+            }
+        }
+    }
+}
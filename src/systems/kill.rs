@@ -14,13 +14,12 @@ use amethyst::{
         ReadStorage,
         System
     },
-    renderer::{
-        SpriteRender,
-        SpriteSheetHandle
-    }
+    renderer::SpriteRender
 };
 use crate::{
+    constants,
     components::{
+        CollisionLayer,
         Expire,
         Killable,
         Rect,
@@ -33,22 +32,28 @@ use crate::{
     resources::{
         GameplayNextState,
         GameplaySessionData,
-        GameState
+        GameRules,
+        GameState,
+        SpriteRef,
+        SpriteSheets
     }
 };
+use rand::prelude::*;
 
 pub struct KillSystem;
 
 impl KillSystem {
     fn drop_pickup<'s>(
         killable_entity: Entity,
+        collision_layers: &mut WriteStorage<'s, CollisionLayer>,
         expires: &mut WriteStorage<'s, Expire>,
         health_pickup_tags: &mut WriteStorage<'s, HealthPickupTag>,
         rects: &mut WriteStorage<'s, Rect>,
         sprite_renders: &mut WriteStorage<'s, SpriteRender>,
         transforms: &mut WriteStorage<'s, Transform>,
         entities: &Entities<'s>,
-        sprite_sheet_handle: SpriteSheetHandle,
+        pickup_sprite: SpriteRender,
+        pickup_expire_time: f64,
         time: &Read<'s, Time>
     ) {
         // Drop pickup
@@ -71,17 +76,12 @@ impl KillSystem {
                 rects
             )
             .with(
-                Expire::new(5.0, time.absolute_real_time_seconds()),
+                Expire::new(pickup_expire_time, time.absolute_real_time_seconds()),
                 expires
             )
-            .with(
-                SpriteRender {
-                    sprite_sheet: sprite_sheet_handle,
-                    sprite_number: 18
-                },
-                sprite_renders
-            )
+            .with(pickup_sprite, sprite_renders)
             .with(HealthPickupTag, health_pickup_tags)
+            .with(CollisionLayer::Pickup, collision_layers)
             .build();
     }
 }
@@ -89,6 +89,7 @@ impl KillSystem {
 impl<'s> System<'s> for KillSystem {
     type SystemData = (
         ReadStorage<'s, PlayerShipTag>,
+        WriteStorage<'s, CollisionLayer>,
         WriteStorage<'s, Expire>,
         WriteStorage<'s, DeleteEntityTag>,
         WriteStorage<'s, HealthPickupTag>,
@@ -99,7 +100,8 @@ impl<'s> System<'s> for KillSystem {
         WriteExpect<'s, GameplayNextState>,
         WriteExpect<'s, GameplaySessionData>,
         Entities<'s>,
-        ReadExpect<'s, SpriteSheetHandle>,
+        ReadExpect<'s, SpriteSheets>,
+        ReadExpect<'s, GameRules>,
         Read<'s, Time>
     );
 
@@ -107,6 +109,7 @@ impl<'s> System<'s> for KillSystem {
         &mut self, 
         (
             player_ship_tags, 
+            mut collision_layers,
             mut expires,
             mut delete_entity_tags,
             mut health_pickup_tags,
@@ -117,33 +120,55 @@ impl<'s> System<'s> for KillSystem {
             mut gameplay_next_state, 
             mut session_data, 
             entities,
-            sprite_sheet_handle,
+            sprite_sheets,
+            game_rules,
             time
         ): Self::SystemData
     ) {
+        // Required sprite, checked while loading
+        let health_pickup_sprite = sprite_sheets
+            .get_sprite_render(&SpriteRef::new(constants::HEALTH_PICKUP_SPRITE.0, constants::HEALTH_PICKUP_SPRITE.1))
+            .expect("Health pickup sprite should have been validated while loading!");
+
+        let mut is_player_killed = false;
         for (killable, entity) in (&mut killables, &entities).join() {
             if !delete_entity_tags.contains(entity) && !killable.is_alive() {
                 if !player_ship_tags.contains(entity) {
-                    session_data.score += killable.get_points();
-                    if killable.is_drops_health() {
+                    if let Some(player_index) = killable.get_last_hit_by_player() {
+                        session_data.add_score(player_index, killable.get_points());
+                        session_data.add_enemy_destroyed();
+                    }
+                    if killable.is_drops_health() && rand::thread_rng().gen_bool(game_rules.health_pickup_drop_chance) {
                         KillSystem::drop_pickup(
                             entity,
+                            &mut collision_layers,
                             &mut expires,
                             &mut health_pickup_tags,
                             &mut rects,
                             &mut sprite_renders,
                             &mut transforms,
                             &entities,
-                            sprite_sheet_handle.clone(),
+                            health_pickup_sprite.clone(),
+                            game_rules.pickup_expire_time,
                             &time
                         );
                     }
                 } else {
-                    gameplay_next_state.next_state = Some(GameState::Finished);
+                    is_player_killed = true;
                 }
 
                 let _ = delete_entity_tags.insert(entity, DeleteEntityTag);
             }
         }
+
+        // Game is over only when every player ship is destroyed
+        if is_player_killed {
+            let is_any_player_alive = (&player_ship_tags, &killables, &entities)
+                .join()
+                .any(|(_, killable, entity)| killable.is_alive() && !delete_entity_tags.contains(entity));
+            if !is_any_player_alive {
+                gameplay_next_state.next_state = Some(GameState::Finished);
+            }
+        }
     }
 }
\ No newline at end of file
@@ -0,0 +1,206 @@
+use amethyst::{
+    core::Time,
+    ecs::{
+        Entities,
+        Entity,
+        Join,
+        Read,
+        ReadExpect,
+        System,
+        WriteExpect,
+        WriteStorage
+    }
+};
+use rand::prelude::*;
+use crate::{
+    components::tags::BossTag,
+    prefabs::{
+        CompletionCondition,
+        EnemyPrefabData,
+        EnemyPrefabs,
+        EnemyPrefabSystemData,
+        LevelData,
+        SimplePrefab
+    },
+    resources::{
+        GameplayNextState,
+        GameplaySessionData,
+        GameRules,
+        GameState
+    }
+};
+
+// Distance from the top of the arena where the boss stops and starts moving sideways
+const BOSS_HOLD_DISTANCE: f32 = 200.0;
+
+// Plays the wave script of a campaign level instead of the endless enemy spawner and finishes
+// the level once its completion condition is met
+pub struct LevelSystem {
+    level: LevelData,
+    elapsed_time: f32,
+    wave_index: usize,
+    wave_start_time: f32,
+    spawned_counts: Vec<u32>,
+    boss: Option<Entity>,
+    is_completed: bool
+}
+
+impl LevelSystem {
+
+    pub fn new(level: LevelData) -> Self {
+        let wave_start_time = level.waves.first().map_or(0.0, |wave| wave.delay);
+        return Self {
+            level,
+            elapsed_time: 0.0,
+            wave_index: 0,
+            wave_start_time,
+            spawned_counts: Vec::new(),
+            boss: None,
+            is_completed: false
+        };
+    }
+
+    // x is a fraction of the arena width, random when not set
+    fn spawn_enemy<'a>(
+        enemy_prefab: &EnemyPrefabData,
+        x: Option<f32>,
+        game_rules: &GameRules,
+        entities: &'a Entities,
+        enemy_system_data: &mut EnemyPrefabSystemData<'a>
+    ) -> Entity {
+        let half_width = enemy_prefab.width / 2.0;
+        let x = match x {
+            Some(x) => half_width + x * (game_rules.arena_width - enemy_prefab.width),
+            None => rand::thread_rng().gen_range(half_width, game_rules.arena_width - half_width)
+        };
+        // -1.0 so it's not deleted by out of bounds system
+        let y = game_rules.arena_height + enemy_prefab.height - 1.0;
+        return enemy_prefab.create_entity(entities, x, y, enemy_system_data);
+    }
+
+}
+
+impl<'s> System<'s> for LevelSystem {
+    type SystemData = (
+        EnemyPrefabSystemData<'s>,
+        WriteStorage<'s, BossTag>,
+        Entities<'s>,
+        ReadExpect<'s, EnemyPrefabs>,
+        ReadExpect<'s, GameRules>,
+        ReadExpect<'s, GameplaySessionData>,
+        WriteExpect<'s, GameplayNextState>,
+        Read<'s, Time>
+    );
+
+    fn run(
+        &mut self,
+        (
+            enemy_system_data,
+            mut boss_tags,
+            entities,
+            enemy_prefabs,
+            game_rules,
+            session_data,
+            mut gameplay_next_state,
+            time
+        ): Self::SystemData
+    ) {
+        if self.is_completed {
+            return;
+        }
+        // Rebound so the storages can be borrowed for less than the system lifetime
+        let mut enemy_system_data = enemy_system_data;
+        self.elapsed_time += time.fixed_seconds() * time.time_scale();
+
+        // Spawns of the current wave, each one is spaced by its interval
+        if let Some(wave) = self.level.waves.get(self.wave_index) {
+            let wave_time = self.elapsed_time - self.wave_start_time;
+            if self.spawned_counts.len() != wave.spawns.len() {
+                self.spawned_counts = vec![0; wave.spawns.len()];
+            }
+            for (spawn, spawned_count) in wave.spawns.iter().zip(self.spawned_counts.iter_mut()) {
+                while *spawned_count < spawn.count && wave_time >= spawn.interval * *spawned_count as f32 {
+                    let enemy_prefab = enemy_prefabs
+                        .get(&spawn.prefab)
+                        .expect("Wave prefabs should have been validated when loading the level!");
+                    LevelSystem::spawn_enemy(enemy_prefab, spawn.x, &game_rules, &entities, &mut enemy_system_data);
+                    *spawned_count += 1;
+                }
+            }
+
+            let is_wave_done = wave.spawns
+                .iter()
+                .zip(&self.spawned_counts)
+                .all(|(spawn, spawned_count)| *spawned_count >= spawn.count);
+            if is_wave_done {
+                self.wave_index += 1;
+                self.spawned_counts.clear();
+                self.wave_start_time = self.elapsed_time + self.level.waves.get(self.wave_index).map_or(0.0, |wave| wave.delay);
+            }
+        }
+        let is_waves_done = self.wave_index >= self.level.waves.len();
+        let enemy_count = (&enemy_system_data.enemy_tags).join().count();
+
+        let is_boss_defeated = match self.boss {
+            Some(boss) => enemy_system_data.killables.get(boss).map_or(true, |killable| !killable.is_alive()),
+            None => false
+        };
+        let is_completed = match self.level.completion {
+            CompletionCondition::ClearWaves => {
+                is_waves_done && (self.level.boss.is_none() || self.boss.is_some()) && enemy_count == 0
+            },
+            CompletionCondition::DefeatBoss => is_boss_defeated,
+            CompletionCondition::Survive { seconds } => self.elapsed_time >= seconds,
+            CompletionCondition::ReachScore { points } => session_data.get_total_score() >= points
+        };
+        // Losing the last ship in the same tick still ends the game
+        let is_game_over = match gameplay_next_state.next_state {
+            Some(GameState::Finished) => true,
+            _ => false
+        };
+        if is_completed && !is_game_over {
+            gameplay_next_state.next_state = Some(GameState::LevelComplete);
+            self.is_completed = true;
+            return;
+        }
+
+        // Boss enters once the arena is clear of the waves
+        let is_boss_due = is_waves_done && enemy_count == 0 && self.boss.is_none();
+        if let (true, Some(boss_prefab)) = (is_boss_due, &self.level.boss) {
+            let enemy_prefab = enemy_prefabs
+                .get(boss_prefab)
+                .expect("Boss prefab should have been validated when loading the level!");
+            let boss = LevelSystem::spawn_enemy(enemy_prefab, Some(0.5), &game_rules, &entities, &mut enemy_system_data);
+            enemy_system_data.destroy_out_of_arena_tags.remove(boss);
+            boss_tags
+                .insert(boss, BossTag)
+                .expect("Could not create BossTag!");
+            self.boss = Some(boss);
+        }
+
+        // Boss stops near the top and moves from side to side
+        if let Some(boss) = self.boss {
+            let (transforms, rects, moveables) = (
+                &enemy_system_data.transforms,
+                &enemy_system_data.rects,
+                &mut enemy_system_data.moveables
+            );
+            if let (Some(transform), Some(rect), Some(moveable)) = (transforms.get(boss), rects.get(boss), moveables.get_mut(boss)) {
+                let x = transform.translation().x;
+                let y = transform.translation().y;
+                if moveable.direction.y != 0.0 && y <= game_rules.arena_height - BOSS_HOLD_DISTANCE {
+                    moveable.direction.x = 1.0;
+                    moveable.direction.y = 0.0;
+                }
+                if moveable.direction.y == 0.0 {
+                    if x - rect.width / 2.0 <= 0.0 {
+                        moveable.direction.x = 1.0;
+                    } else if x + rect.width / 2.0 >= game_rules.arena_width {
+                        moveable.direction.x = -1.0;
+                    }
+                }
+            }
+        }
+    }
+
+}
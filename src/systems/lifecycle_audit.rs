@@ -0,0 +1,196 @@
+use std::collections::{
+    HashMap,
+    HashSet
+};
+use amethyst::{
+    core::{
+        Parent,
+        Transform
+    },
+    ecs::{
+        Entities,
+        Entity,
+        Join,
+        ReadStorage,
+        System
+    },
+    renderer::{
+        Camera,
+        SpriteRender
+    },
+    ui::{
+        UiImage,
+        UiText,
+        UiTransform
+    }
+};
+use log::warn;
+use crate::components::{
+    Cannon,
+    Expire,
+    HealthBar,
+    Killable,
+    Missile,
+    tags::{
+        BackgroundTag,
+        DeleteEntityTag,
+        EnemyTag,
+        HealthPickupTag,
+        PlayerShipTag
+    }
+};
+
+// Ticks an entity may stay unreachable, entities are briefly unreachable while being created
+const UNREACHABLE_TICKS: u32 = 60;
+
+// Debug builds only, reports entities which are not reachable from any root and stored entity
+// references pointing to dead entities
+#[derive(Default)]
+pub struct LifecycleAuditSystem {
+    unreachable_ticks: HashMap<Entity, u32>,
+    reported_references: HashSet<(Entity, Entity)>
+}
+
+impl LifecycleAuditSystem {
+
+    fn report_dead_reference(&mut self, owner: Entity, referenced: Entity, reference: &str) {
+        if self.reported_references.insert((owner, referenced)) {
+            warn!("Entity {:?} references {} {:?} which is dead", owner, reference, referenced);
+        }
+    }
+
+}
+
+impl<'s> System<'s> for LifecycleAuditSystem {
+    type SystemData = (
+        ReadStorage<'s, PlayerShipTag>,
+        ReadStorage<'s, EnemyTag>,
+        ReadStorage<'s, Missile>,
+        ReadStorage<'s, HealthPickupTag>,
+        ReadStorage<'s, Expire>,
+        ReadStorage<'s, Camera>,
+        ReadStorage<'s, BackgroundTag>,
+        ReadStorage<'s, DeleteEntityTag>,
+        ReadStorage<'s, Killable>,
+        ReadStorage<'s, Cannon>,
+        ReadStorage<'s, HealthBar>,
+        ReadStorage<'s, Parent>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, SpriteRender>,
+        ReadStorage<'s, UiTransform>,
+        ReadStorage<'s, UiImage>,
+        ReadStorage<'s, UiText>,
+        Entities<'s>
+    );
+
+    fn run(
+        &mut self,
+        (
+            player_ship_tags,
+            enemy_tags,
+            missiles,
+            health_pickup_tags,
+            expires,
+            cameras,
+            background_tags,
+            delete_entity_tags,
+            killables,
+            cannons,
+            health_bars,
+            parents,
+            transforms,
+            sprite_renders,
+            ui_transforms,
+            ui_images,
+            ui_texts,
+            entities
+        ): Self::SystemData
+    ) {
+        // Gameplay roots and UI, anything else has to be owned by them
+        let mut reachable: HashSet<Entity> = (&entities)
+            .join()
+            .filter(|entity| {
+                player_ship_tags.contains(*entity) ||
+                enemy_tags.contains(*entity) ||
+                missiles.contains(*entity) ||
+                health_pickup_tags.contains(*entity) ||
+                expires.contains(*entity) ||
+                cameras.contains(*entity) ||
+                background_tags.contains(*entity) ||
+                (ui_transforms.contains(*entity) && !parents.contains(*entity))
+            })
+            .collect();
+
+        // Follow stored references and parents until nothing new is reached
+        let mut is_changed = true;
+        while is_changed {
+            let previous_count = reachable.len();
+
+            let mut referenced: Vec<Entity> = Vec::new();
+            for (entity, killable) in (&entities, &killables).join() {
+                if reachable.contains(&entity) {
+                    referenced.extend(killable.health_bar_entity);
+                }
+            }
+            for (entity, parent) in (&entities, &parents).join() {
+                if reachable.contains(&parent.entity) {
+                    referenced.push(entity);
+                }
+            }
+            reachable.extend(referenced.into_iter().filter(|entity| entities.is_alive(*entity)));
+
+            is_changed = reachable.len() != previous_count;
+        }
+
+        let mut unreachable_ticks = HashMap::new();
+        for entity in (&entities).join() {
+            if reachable.contains(&entity) || delete_entity_tags.contains(entity) {
+                continue;
+            }
+
+            let ticks = self.unreachable_ticks.get(&entity).cloned().unwrap_or(0) + 1;
+            if ticks == UNREACHABLE_TICKS {
+                let components: Vec<&str> = [
+                    ("Transform", transforms.contains(entity)),
+                    ("SpriteRender", sprite_renders.contains(entity)),
+                    ("Parent", parents.contains(entity)),
+                    ("Killable", killables.contains(entity)),
+                    ("Cannon", cannons.contains(entity)),
+                    ("UiTransform", ui_transforms.contains(entity)),
+                    ("UiImage", ui_images.contains(entity)),
+                    ("UiText", ui_texts.contains(entity)),
+                    ("HealthBar", health_bars.contains(entity))
+                ]
+                    .iter()
+                    .filter(|(_, has_component)| *has_component)
+                    .map(|(name, _)| *name)
+                    .collect();
+                warn!(
+                    "Entity {:?} is unreachable from any root for {} ticks, components: [{}]",
+                    entity,
+                    UNREACHABLE_TICKS,
+                    components.join(", ")
+                );
+            }
+            unreachable_ticks.insert(entity, ticks);
+        }
+        self.unreachable_ticks = unreachable_ticks;
+
+        for (entity, killable) in (&entities, &killables).join() {
+            if let Some(health_bar_entity) = killable.health_bar_entity {
+                if !entities.is_alive(health_bar_entity) {
+                    self.report_dead_reference(entity, health_bar_entity, "health bar");
+                }
+            }
+        }
+        for (entity, health_bar) in (&entities, &health_bars).join() {
+            for sprite_entity in &health_bar.sprite_entities {
+                if !entities.is_alive(*sprite_entity) {
+                    self.report_dead_reference(entity, *sprite_entity, "health bar sprite");
+                }
+            }
+        }
+        self.reported_references.retain(|(entity, _)| entities.is_alive(*entity));
+    }
+
+}
@@ -1,31 +1,55 @@
 mod bound_in_arena;
-mod clear_children;
+mod collision;
+mod collision_grid;
+mod console;
+mod console_commands;
+mod damage;
+mod debug_overlay;
+mod debris;
 mod delete_entities;
 mod destroy_out_of_arena;
-mod enemy_collision;
 mod enemy_spawner;
 mod expire;
+mod health_bar;
+mod hot_reload;
 mod kill;
-mod missile;
+mod level;
+mod lifecycle_audit;
 mod movement;
+mod physics;
 mod pickups;
 mod player_ship_input;
+mod regeneration;
 mod shoot;
+mod timed;
 mod ui;
+mod viewport;
 
 pub use {
     bound_in_arena::BoundInArenaSystem,
-    clear_children::ClearChildrenSystem,
+    collision::CollisionSystem,
+    collision_grid::CollisionGridSystem,
+    console::ConsoleSystem,
+    console_commands::ConsoleCommandSystem,
+    damage::DamageSystem,
+    debug_overlay::DebugOverlaySystem,
+    debris::DebrisSystem,
     delete_entities::DeleteEntitiesSystem,
     destroy_out_of_arena::DestroyOutOfArenaSystem,
-    enemy_collision::EnemyCollisionSystem,
     enemy_spawner::EnemySpawnerSystem,
     expire::ExpireSystem,
+    health_bar::HealthBarSystem,
+    hot_reload::HotReloadSystem,
     kill::KillSystem,
-    missile::MissileSystem,
+    level::LevelSystem,
+    lifecycle_audit::LifecycleAuditSystem,
     movement::MovementSystem,
+    physics::PhysicsSystem,
     pickups::PickupsSystem,
     player_ship_input::PlayerShipSystem,
+    regeneration::RegenerationSystem,
     shoot::ShootingSystem,
-    ui::UiSystem
+    timed::TimedSystem,
+    ui::UiSystem,
+    viewport::ViewportSystem
 };
\ No newline at end of file
@@ -0,0 +1,137 @@
+use amethyst::{
+    core::{
+        nalgebra::Vector2,
+        Time,
+        Transform
+    },
+    ecs::{
+        Entity,
+        Join,
+        Read,
+        ReadStorage,
+        Resources,
+        System,
+        SystemData,
+        WriteStorage
+    },
+    shrev::{
+        EventChannel,
+        ReaderId
+    }
+};
+use crate::{
+    components::{
+        Moveable,
+        RigidBody
+    },
+    events::CollisionEvent
+};
+
+const RESTITUTION: f32 = 0.5;
+
+#[derive(Default)]
+pub struct PhysicsSystem {
+    collision_event_reader: Option<ReaderId<CollisionEvent>>
+}
+
+impl PhysicsSystem {
+
+    fn get_velocity<'s>(
+        entity: Entity,
+        rigid_bodies: &WriteStorage<'s, RigidBody>,
+        moveables: &ReadStorage<'s, Moveable>
+    ) -> Vector2<f32> {
+        let mut velocity = rigid_bodies.get(entity).unwrap().velocity;
+        if let Some(moveable) = moveables.get(entity) {
+            velocity += moveable.direction * moveable.move_speed;
+        }
+
+        return velocity;
+    }
+
+    // Exchanges momentum between two bodies along the line connecting their centers
+    fn resolve_collision<'s>(
+        first: Entity,
+        second: Entity,
+        rigid_bodies: &mut WriteStorage<'s, RigidBody>,
+        moveables: &ReadStorage<'s, Moveable>,
+        transforms: &WriteStorage<'s, Transform>
+    ) {
+        if !rigid_bodies.contains(first) || !rigid_bodies.contains(second) {
+            return;
+        }
+        let (first_transform, second_transform) = match (transforms.get(first), transforms.get(second)) {
+            (Some(first_transform), Some(second_transform)) => (first_transform, second_transform),
+            _ => return
+        };
+
+        let offset = second_transform.translation() - first_transform.translation();
+        let normal = Vector2::new(offset.x, offset.y);
+        if normal.norm_squared() == 0.0 {
+            return;
+        }
+        let normal = normal.normalize();
+
+        let relative_velocity = PhysicsSystem::get_velocity(second, rigid_bodies, moveables) - PhysicsSystem::get_velocity(first, rigid_bodies, moveables);
+        let approach_speed = relative_velocity.dot(&normal);
+        // Bodies are already moving apart
+        if approach_speed >= 0.0 {
+            return;
+        }
+
+        let first_inverse_mass = 1.0 / rigid_bodies.get(first).unwrap().mass;
+        let second_inverse_mass = 1.0 / rigid_bodies.get(second).unwrap().mass;
+        let impulse = normal * (-(1.0 + RESTITUTION) * approach_speed / (first_inverse_mass + second_inverse_mass));
+
+        rigid_bodies.get_mut(first).unwrap().apply_impulse(-impulse);
+        rigid_bodies.get_mut(second).unwrap().apply_impulse(impulse);
+    }
+
+}
+
+impl<'s> System<'s> for PhysicsSystem {
+    type SystemData = (
+        ReadStorage<'s, Moveable>,
+        WriteStorage<'s, RigidBody>,
+        WriteStorage<'s, Transform>,
+        Read<'s, EventChannel<CollisionEvent>>,
+        Read<'s, Time>
+    );
+
+    fn setup(&mut self, res: &mut Resources) {
+        Self::SystemData::setup(res);
+        self.collision_event_reader = Some(res.fetch_mut::<EventChannel<CollisionEvent>>().register_reader());
+    }
+
+    fn run(
+        &mut self,
+        (
+            moveables,
+            mut rigid_bodies,
+            mut transforms,
+            collision_events,
+            time
+        ): Self::SystemData
+    ) {
+        for event in collision_events.read(self.collision_event_reader.as_mut().unwrap()) {
+            if let CollisionEvent::CollisionStarted(collision) = event {
+                let (first, second) = collision.get_pair();
+                PhysicsSystem::resolve_collision(first, second, &mut rigid_bodies, &moveables, &transforms);
+            }
+        }
+
+        // Runs inside fixed_update, so it always steps by the fixed delta scaled like the rest of the game.
+        // Position is read from Transform every step, so gameplay systems can still move bodies directly
+        let delta = time.fixed_seconds() * time.time_scale();
+        for (rigid_body, transform) in (&mut rigid_bodies, &mut transforms).join() {
+            let movement = rigid_body.velocity * delta;
+            transform.translate_xyz(movement.x, movement.y, 0.0);
+            if rigid_body.angular_velocity != 0.0 {
+                transform.roll_local(rigid_body.angular_velocity * delta);
+            }
+
+            rigid_body.velocity *= (1.0 - rigid_body.linear_damping * delta).max(0.0);
+            rigid_body.angular_velocity *= (1.0 - rigid_body.angular_damping * delta).max(0.0);
+        }
+    }
+}
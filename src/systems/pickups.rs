@@ -1,60 +1,64 @@
 use amethyst::{
-    core::Transform,
     ecs::{
-        Entities,
-        Join,
-        ReadStorage,
+        Read,
+        Resources,
         System,
+        SystemData,
         WriteStorage
+    },
+    shrev::{
+        EventChannel,
+        ReaderId
     }
 };
 use crate::{
     components::{
+        CollisionLayer,
         Killable,
-        Rect,
-        tags::{
-            DeleteEntityTag,
-            HealthPickupTag,
-            PlayerShipTag
-        }
+        tags::DeleteEntityTag
     },
-    utils
+    events::CollisionEvent
 };
 
-pub struct PickupsSystem;
+#[derive(Default)]
+pub struct PickupsSystem {
+    collision_event_reader: Option<ReaderId<CollisionEvent>>
+}
 
 impl<'s> System<'s> for PickupsSystem {
     type SystemData = (
-        ReadStorage<'s, HealthPickupTag>,
-        ReadStorage<'s, PlayerShipTag>,
-        ReadStorage<'s, Rect>,
-        ReadStorage<'s, Transform>,
         WriteStorage<'s, DeleteEntityTag>,
         WriteStorage<'s, Killable>,
-        Entities<'s>
+        Read<'s, EventChannel<CollisionEvent>>
     );
 
+    fn setup(&mut self, res: &mut Resources) {
+        Self::SystemData::setup(res);
+        self.collision_event_reader = Some(res.fetch_mut::<EventChannel<CollisionEvent>>().register_reader());
+    }
+
     fn run(
         &mut self,
         (
-            health_pickup_tags,
-            player_ship_tags,
-            rects,
-            transforms,
             mut delete_entity_tags,
             mut killables,
-            entities
+            collision_events
         ): Self::SystemData
     ) {
-        for (player_rect, player_transform, player_killable, _) in (&rects, &transforms, &mut killables, &player_ship_tags).join() {
-            for (pickup_rect, pickup_transform, pickup_entity, _) in (&rects, &transforms, &entities, &health_pickup_tags).join() {
-                if !delete_entity_tags.contains(pickup_entity) && utils::is_aabb_collide(player_rect, player_transform, pickup_rect, pickup_transform) {
-                    player_killable.gain_health();
-                    let _ = delete_entity_tags.insert(pickup_entity, DeleteEntityTag);
+        for event in collision_events.read(self.collision_event_reader.as_mut().unwrap()) {
+            if let CollisionEvent::CollisionStarted(collision) = event {
+                if let Some((player_entity, pickup_entity)) = collision.get_entities(CollisionLayer::Player, CollisionLayer::Pickup) {
+                    if delete_entity_tags.contains(player_entity) || delete_entity_tags.contains(pickup_entity) {
+                        continue;
+                    }
+
+                    if let Some(player_killable) = killables.get_mut(player_entity) {
+                        player_killable.gain_health();
+                        let _ = delete_entity_tags.insert(pickup_entity, DeleteEntityTag);
+                    }
                 }
             }
         }
-
     }
 
-}
\ No newline at end of file
+}
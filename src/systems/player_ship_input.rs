@@ -9,12 +9,16 @@ use amethyst::{
     input::InputHandler
 };
 
-use crate::components::{
-    Moveable,
-    SpaceShip,
-    tags::{
-        PlayerShipTag
-    }
+use crate::{
+    components::{
+        Moveable,
+        Player,
+        SpaceShip,
+        tags::{
+            PlayerShipTag
+        }
+    },
+    resources::Console
 };
 
 pub struct PlayerShipSystem;
@@ -23,14 +27,18 @@ impl<'s> System<'s> for PlayerShipSystem {
     type SystemData = (
         WriteStorage<'s, Moveable>,
         WriteStorage<'s, SpaceShip>,
+        ReadStorage<'s, Player>,
         ReadStorage<'s, PlayerShipTag>,
-        Read<'s, InputHandler<String, String>>
+        Read<'s, InputHandler<String, String>>,
+        Read<'s, Console>
     );
 
-    fn run(&mut self, (mut moveables, mut space_ships, player_ship, input): Self::SystemData) {
-        for (moveable, space_ship, _) in (&mut moveables, &mut space_ships, &player_ship).join() {
-            let x_movement = input.axis_value("x_axis").unwrap_or(0.0);
-            let y_movement = input.axis_value("y_axis").unwrap_or(0.0);
+    fn run(&mut self, (mut moveables, mut space_ships, players, player_ship, input, console): Self::SystemData) {
+        // Keys typed into the console do not control the ships
+        let is_input_enabled = !console.is_open;
+        for (moveable, space_ship, player, _) in (&mut moveables, &mut space_ships, &players, &player_ship).join() {
+            let x_movement = input.axis_value(player.get_x_axis_binding()).filter(|_| is_input_enabled).unwrap_or(0.0);
+            let y_movement = input.axis_value(player.get_y_axis_binding()).filter(|_| is_input_enabled).unwrap_or(0.0);
 
             moveable.direction.x = x_movement as f32;
             moveable.direction.y = y_movement as f32;
@@ -39,7 +47,7 @@ impl<'s> System<'s> for PlayerShipSystem {
             }
 
             // Whether to shoot next frame
-            space_ship.is_attacking = input.action_is_down("fire").unwrap_or(false);
+            space_ship.is_attacking = is_input_enabled && input.action_is_down(player.get_fire_binding()).unwrap_or(false);
         }
     }
 }
\ No newline at end of file
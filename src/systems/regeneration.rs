@@ -0,0 +1,40 @@
+use amethyst::{
+    core::Time,
+    ecs::{
+        Join,
+        Read,
+        System,
+        WriteStorage
+    }
+};
+use crate::components::{
+    Killable,
+    Regeneration
+};
+
+pub struct RegenerationSystem;
+
+impl<'s> System<'s> for RegenerationSystem {
+    type SystemData = (
+        WriteStorage<'s, Regeneration>,
+        WriteStorage<'s, Killable>,
+        Read<'s, Time>
+    );
+
+    fn run(
+        &mut self,
+        (
+            mut regenerations,
+            mut killables,
+            time
+        ): Self::SystemData
+    ) {
+        let delta_seconds = time.fixed_seconds() * time.time_scale();
+        for (regeneration, killable) in (&mut regenerations, &mut killables).join() {
+            if regeneration.tick(delta_seconds) && killable.is_alive() {
+                killable.gain_health();
+            }
+        }
+    }
+
+}
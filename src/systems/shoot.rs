@@ -9,22 +9,21 @@ use amethyst::{
         Entities,
         Join,
         Read,
-        ReadExpect,
         ReadStorage,
         System,
         WriteStorage
     },
-    renderer::{
-        SpriteRender, 
-        SpriteSheetHandle
-    }
+    renderer::SpriteRender
 };
 use crate::{
     components::{
         Cannon,
+        CollisionLayer,
         Missile,
         Moveable,
+        Player,
         Rect,
+        RigidBody,
         SpaceShip,
         tags::{
             PlayerShipTag,
@@ -33,12 +32,16 @@ use crate::{
     }
 };
 
+// Light compared to ships, so hits only nudge them
+const MISSILE_MASS: f32 = 0.1;
+
 pub struct ShootingSystem;
 
 impl<'s> System<'s> for ShootingSystem {
     type SystemData = (
         ReadStorage<'s, SpaceShip>,
         ReadStorage<'s, PlayerShipTag>,
+        ReadStorage<'s, Player>,
         ReadStorage<'s, Parent>,
         WriteStorage<'s, Cannon>,
         WriteStorage<'s, Transform>,
@@ -47,7 +50,8 @@ impl<'s> System<'s> for ShootingSystem {
         WriteStorage<'s, Moveable>,
         WriteStorage<'s, Rect>,
         WriteStorage<'s, DestroyOutOfArenaTag>,
-        ReadExpect<'s, SpriteSheetHandle>,
+        WriteStorage<'s, CollisionLayer>,
+        WriteStorage<'s, RigidBody>,
         Entities<'s>,
         Read<'s, Time>
     );
@@ -57,6 +61,7 @@ impl<'s> System<'s> for ShootingSystem {
         (
             space_ships, 
             player_ship_tags, 
+            players,
             parents,
             mut cannons,
             mut transforms, 
@@ -65,7 +70,8 @@ impl<'s> System<'s> for ShootingSystem {
             mut moveables,
             mut rects,
             mut destroy_out_of_arena_tags,
-            sprite_sheet_handle, 
+            mut collision_layers,
+            mut rigid_bodies,
             entities,
             time
         ): Self::SystemData
@@ -77,23 +83,20 @@ impl<'s> System<'s> for ShootingSystem {
                 transform.translate_xyz(cannon.x_offset, cannon.y_offset, 0.0);
                 // TODO! Transform has modified scale, it affects missile size!
                 let is_player = player_ship_tags.contains(parent.entity);
-                let direction_y = if is_player {
-                    1.0
+                let (direction_y, collision_layer) = if is_player {
+                    (1.0, CollisionLayer::PlayerProjectile)
                 } else {
-                    -1.0
+                    (-1.0, CollisionLayer::EnemyProjectile)
                 };
 
                 entities
                     .build_entity()
                     .with(transform, &mut transforms)
+                    .with(cannon.missile_sprite.clone(), &mut sprite_renders)
                     .with(
-                        SpriteRender {
-                            sprite_sheet: sprite_sheet_handle.clone(),
-                            sprite_number: cannon.missile_sprite_index
-                        }, 
-                        &mut sprite_renders
+                        Missile::new(players.get(parent.entity).map(|player| player.get_index())),
+                        &mut missiles
                     )
-                    .with(Missile::new(is_player), &mut missiles)
                     .with(
                         Moveable {
                             move_speed: cannon.missile_speed,
@@ -109,6 +112,8 @@ impl<'s> System<'s> for ShootingSystem {
                         &mut rects
                     )
                     .with(DestroyOutOfArenaTag, &mut destroy_out_of_arena_tags)
+                    .with(collision_layer, &mut collision_layers)
+                    .with(RigidBody::new(MISSILE_MASS), &mut rigid_bodies)
                     .build();
 
                 cannon.last_attack_time = time.absolute_time_seconds();
@@ -0,0 +1,45 @@
+use std::time::Instant;
+use amethyst::ecs::{
+    Read,
+    Resources,
+    System,
+    SystemData
+};
+use crate::resources::SystemTimings;
+
+// Wraps a system to record how long each run takes, only reads the timings resource so wrapped
+// systems still run in parallel
+pub struct TimedSystem<S> {
+    name: &'static str,
+    system: S
+}
+
+impl<S> TimedSystem<S> {
+
+    pub fn new(name: &'static str, system: S) -> Self {
+        return Self {
+            name,
+            system
+        };
+    }
+
+}
+
+impl<'s, S: System<'s>> System<'s> for TimedSystem<S> {
+    type SystemData = (
+        S::SystemData,
+        Read<'s, SystemTimings>
+    );
+
+    fn setup(&mut self, res: &mut Resources) {
+        self.system.setup(res);
+        <Read<'s, SystemTimings> as SystemData>::setup(res);
+    }
+
+    fn run(&mut self, (system_data, system_timings): Self::SystemData) {
+        let start = Instant::now();
+        self.system.run(system_data);
+        system_timings.record(self.name, start.elapsed());
+    }
+
+}
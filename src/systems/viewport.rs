@@ -0,0 +1,220 @@
+use amethyst::{
+    assets::{
+        AssetStorage,
+        Handle,
+        Loader
+    },
+    ecs::{
+        Entities,
+        Entity,
+        Join,
+        Read,
+        ReadExpect,
+        ReadStorage,
+        System,
+        Write,
+        WriteStorage
+    },
+    input::InputHandler,
+    renderer::{
+        Camera,
+        Projection,
+        ScreenDimensions,
+        Texture,
+        TextureData,
+        WindowMessages
+    },
+    ui::{
+        Anchor,
+        Stretch,
+        UiImage,
+        UiTransform
+    }
+};
+use crate::{
+    components::tags::ViewportAnchorTag,
+    constants,
+    resources::{
+        ArenaViewport,
+        GameOptions,
+        GameRules
+    }
+};
+
+// Keeps the arena in its logical space whatever the window size, the arena is scaled to fit the
+// window and the remaining space is covered by letterbox or pillarbox bars
+#[derive(Default)]
+pub struct ViewportSystem {
+    bars: Option<(Entity, Entity)>,
+    bar_texture: Option<Handle<Texture>>,
+    // Fullscreen state of the window, follows the option
+    is_fullscreen: bool,
+    was_fullscreen_key_down: bool
+}
+
+impl ViewportSystem {
+
+    fn set_fullscreen(&mut self, is_fullscreen: bool, window_messages: &mut WindowMessages) {
+        self.is_fullscreen = is_fullscreen;
+
+        window_messages.send_command(move |window| {
+            if is_fullscreen {
+                window.set_fullscreen(Some(window.get_current_monitor()));
+            } else {
+                window.set_fullscreen(None);
+            }
+        });
+    }
+
+    fn create_bar(
+        id: &str,
+        texture: Handle<Texture>,
+        entities: &Entities,
+        ui_transforms: &mut WriteStorage<UiTransform>,
+        ui_images: &mut WriteStorage<UiImage>
+    ) -> Entity {
+        let bar = entities.create();
+        ui_transforms
+            .insert(bar, UiTransform::new(String::from(id), Anchor::Middle, 0.0, 0.0, 100.0, 0.0, 0.0, 1).as_transparent())
+            .expect("Could not create UiTransform!");
+        ui_images
+            .insert(bar, UiImage { texture })
+            .expect("Could not create UiImage!");
+        return bar;
+    }
+
+    // Bars are placed against the window edges, local position moves their centre inside
+    fn place_bar(ui_transform: &mut UiTransform, anchor: Anchor, x: f32, y: f32, width: f32, height: f32) {
+        ui_transform.anchor = anchor;
+        ui_transform.local_x = x;
+        ui_transform.local_y = y;
+        ui_transform.width = width;
+        ui_transform.height = height;
+    }
+
+}
+
+impl<'s> System<'s> for ViewportSystem {
+    type SystemData = (
+        WriteStorage<'s, Camera>,
+        WriteStorage<'s, UiTransform>,
+        WriteStorage<'s, UiImage>,
+        ReadStorage<'s, ViewportAnchorTag>,
+        Entities<'s>,
+        ReadExpect<'s, ScreenDimensions>,
+        ReadExpect<'s, GameRules>,
+        ReadExpect<'s, Loader>,
+        Read<'s, AssetStorage<Texture>>,
+        Read<'s, InputHandler<String, String>>,
+        Write<'s, WindowMessages>,
+        Write<'s, ArenaViewport>,
+        Write<'s, GameOptions>
+    );
+
+    fn run(
+        &mut self,
+        (
+            mut cameras,
+            mut ui_transforms,
+            mut ui_images,
+            viewport_anchor_tags,
+            entities,
+            screen_dimensions,
+            game_rules,
+            loader,
+            texture_storage,
+            input,
+            mut window_messages,
+            mut arena_viewport,
+            mut game_options
+        ): Self::SystemData
+    ) {
+        let is_fullscreen_key_down = input.action_is_down(constants::FULLSCREEN_BINDING).unwrap_or(false);
+        if is_fullscreen_key_down && !self.was_fullscreen_key_down {
+            game_options.is_fullscreen = !game_options.is_fullscreen;
+        }
+        self.was_fullscreen_key_down = is_fullscreen_key_down;
+        if game_options.is_fullscreen != self.is_fullscreen {
+            self.set_fullscreen(game_options.is_fullscreen, &mut window_messages);
+        }
+
+        let screen_width = screen_dimensions.width();
+        let screen_height = screen_dimensions.height();
+        if screen_width <= 0.0 || screen_height <= 0.0 {
+            // Minimised window
+            return;
+        }
+
+        // Margins of the arena viewport, in arena units for the camera and in pixels for the UI
+        let arena_aspect_ratio = game_rules.arena_width / game_rules.arena_height;
+        let screen_aspect_ratio = screen_width / screen_height;
+        let is_pillarbox = screen_aspect_ratio > arena_aspect_ratio;
+        let (arena_x_margin, arena_y_margin, x_margin, y_margin) = if is_pillarbox {
+            let visible_width = game_rules.arena_height * screen_aspect_ratio;
+            (
+                (visible_width - game_rules.arena_width) / 2.0,
+                0.0,
+                (screen_width - screen_height * arena_aspect_ratio) / 2.0,
+                0.0
+            )
+        } else {
+            let visible_height = game_rules.arena_width / screen_aspect_ratio;
+            (
+                0.0,
+                (visible_height - game_rules.arena_height) / 2.0,
+                0.0,
+                (screen_height - screen_width / arena_aspect_ratio) / 2.0
+            )
+        };
+
+        arena_viewport.x_offset = x_margin;
+        arena_viewport.y_offset = y_margin;
+        arena_viewport.scale = if is_pillarbox {
+            screen_height / game_rules.arena_height
+        } else {
+            screen_width / game_rules.arena_width
+        };
+
+        for camera in (&mut cameras).join() {
+            camera.proj = Projection::orthographic(
+                -arena_x_margin,
+                game_rules.arena_width + arena_x_margin,
+                -arena_y_margin,
+                game_rules.arena_height + arena_y_margin
+            ).into();
+        }
+
+        for (ui_transform, _) in (&mut ui_transforms, &viewport_anchor_tags).join() {
+            ui_transform.stretch = Some(Stretch::XY { x_margin, y_margin });
+        }
+
+        // States deleting all entities also delete the bars
+        let bars_alive = self.bars.map_or(false, |(first, second)| entities.is_alive(first) && entities.is_alive(second));
+        if !bars_alive {
+            let texture = self.bar_texture
+                .get_or_insert_with(|| loader.load_from_data(TextureData::from(constants::LETTERBOX_COLOR), (), &texture_storage))
+                .clone();
+            self.bars = Some((
+                ViewportSystem::create_bar("letterbox_first_bar", texture.clone(), &entities, &mut ui_transforms, &mut ui_images),
+                ViewportSystem::create_bar("letterbox_second_bar", texture, &entities, &mut ui_transforms, &mut ui_images)
+            ));
+        }
+
+        let (first_bar, second_bar) = self.bars.unwrap();
+        if let Some(ui_transform) = ui_transforms.get_mut(first_bar) {
+            if is_pillarbox {
+                ViewportSystem::place_bar(ui_transform, Anchor::MiddleLeft, x_margin / 2.0, 0.0, x_margin, screen_height);
+            } else {
+                ViewportSystem::place_bar(ui_transform, Anchor::TopMiddle, 0.0, -y_margin / 2.0, screen_width, y_margin);
+            }
+        }
+        if let Some(ui_transform) = ui_transforms.get_mut(second_bar) {
+            if is_pillarbox {
+                ViewportSystem::place_bar(ui_transform, Anchor::MiddleRight, -x_margin / 2.0, 0.0, x_margin, screen_height);
+            } else {
+                ViewportSystem::place_bar(ui_transform, Anchor::BottomMiddle, 0.0, y_margin / 2.0, screen_width, y_margin);
+            }
+        }
+    }
+
+}